@@ -159,6 +159,11 @@ p3_table:
 p2_table:
     resb 4096
 
+; Left unmapped once the kernel remaps itself, so a stack overflow page faults
+global stack_guard_page
+stack_guard_page:
+    resb 4096
+
 ; Stack
 stack_bottom:
 	resb 4096 * 4
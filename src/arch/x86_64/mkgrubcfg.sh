@@ -1,13 +1,33 @@
+# Kernel options like layout=us can be put in kernel.cmdline
+kernel_cmdline=""
+if [ -f "$1/kernel.cmdline" ]; then
+	kernel_cmdline=" `cat \"$1/kernel.cmdline\"`"
+fi
 echo """
 set timeout=0
 set default=0
+insmod all_video
 
 menuentry "my os" {
-    multiboot2 /boot/kernel.bin
+    multiboot2 /boot/kernel.bin$kernel_cmdline
 """
 for file in $1/*.out; do
+	# The pattern stays unexpanded when there are no modules
+	[ -f "$file" ] || continue
 	filename=`basename $file`
-	echo "    module2 /boot/$filename `echo \"$filename\" | sed s/.out$//`"
+	name=`echo "$filename" | sed s/.out$//`
+	# Extra words for the module command line can be put in name.cmdline
+	cmdline=""
+	if [ -f "$1/$name.cmdline" ]; then
+		cmdline=" `cat \"$1/$name.cmdline\"`"
+	fi
+	echo "    module2 /boot/$filename $name$cmdline"
+done
+# name.in is typed on stdin of name
+for file in $1/*.in; do
+	[ -f "$file" ] || continue
+	filename=`basename $file`
+	echo "    module2 /boot/$filename $filename"
 done
 echo """
     boot
@@ -6,7 +6,17 @@ header_start:
     ; checksum
     dd 0x100000000 - (0xe85250d6 + 0 + (header_end - header_start))
 
-    ; insert optional multiboot tags here
+    ; optional framebuffer tag: 1024x768 at 32 bits per pixel is 128x48
+    ; characters, the kernel stays in VGA text mode if it isn't honoured
+    align 8
+    dw 5    ; type
+    dw 1    ; flags (optional)
+    dd 20   ; size
+    dd 1024 ; width
+    dd 768  ; height
+    dd 32   ; depth
+
+    align 8
 
     ; required end tag
     dw 0    ; type
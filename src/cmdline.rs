@@ -0,0 +1,170 @@
+// Kernel options, `key=value` words after the kernel on the multiboot2 line
+// of grub.cfg. They are parsed once at boot and can be read from anywhere.
+
+use alloc::string::{String, ToString};
+use spin::Once;
+
+use crate::keyboard::Layout;
+use crate::println;
+
+static CONFIG: Once<Config> = Once::new();
+static DEFAULT_CONFIG: Config = DEFAULT;
+
+const DEFAULT: Config = Config {
+    autorun: None,
+    layout: Layout::Azerty,
+    serial: true,
+    loglevel: LogLevel::Info,
+    budget: None,
+    trace: false,
+    stdin: None,
+    on_exit: OnExit::Halt,
+};
+
+// Which kernel messages are shown, each level includes the ones before it
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+pub enum LogLevel {
+    Error = 0,
+    Warn,
+    Info,
+    Debug,
+}
+
+impl LogLevel {
+    fn from_name(name: &str) -> Option<LogLevel> {
+        match name {
+            "error" => Some(LogLevel::Error),
+            "warn" => Some(LogLevel::Warn),
+            "info" => Some(LogLevel::Info),
+            "debug" => Some(LogLevel::Debug),
+            _ => None,
+        }
+    }
+}
+
+// What happens when the program stops
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum OnExit {
+    Halt,
+    Menu,
+    PowerOff,
+}
+
+impl OnExit {
+    fn from_name(name: &str) -> Option<OnExit> {
+        match name {
+            "halt" => Some(OnExit::Halt),
+            "menu" => Some(OnExit::Menu),
+            "poweroff" => Some(OnExit::PowerOff),
+            _ => None,
+        }
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct Config {
+    // Program to start without the menu
+    pub autorun: Option<String>,
+    pub layout: Layout,
+    // Whether the console is mirrored to COM1
+    pub serial: bool,
+    pub loglevel: LogLevel,
+    // Instructions a program may run before it is stopped, None for no limit
+    pub budget: Option<u64>,
+    // Print every RCPU instruction to COM1
+    pub trace: bool,
+    // Module used as stdin of every program instead of <program>.in
+    pub stdin: Option<String>,
+    pub on_exit: OnExit,
+}
+
+impl Config {
+    pub fn parse(command_line: &str) -> Config {
+        let mut config = DEFAULT;
+        for word in command_line.split_whitespace() {
+            match word.split_once('=') {
+                Some(("autorun", name)) => config.autorun = Some(name.to_string()),
+                Some(("layout", name)) => match Layout::from_name(name) {
+                    Some(layout) => config.layout = layout,
+                    None => println!("Unknown keyboard layout {}", name),
+                },
+                Some(("serial", value)) => match parse_switch(value) {
+                    Some(serial) => config.serial = serial,
+                    None => println!("Invalid serial value {}", value),
+                },
+                Some(("loglevel", value)) => match LogLevel::from_name(value) {
+                    Some(loglevel) => config.loglevel = loglevel,
+                    None => println!("Invalid loglevel {}", value),
+                },
+                Some(("budget", value)) => match value.parse() {
+                    Ok(budget) => config.budget = Some(budget),
+                    Err(_) => println!("Invalid budget {}", value),
+                },
+                Some(("trace", value)) => match parse_switch(value) {
+                    Some(trace) => config.trace = trace,
+                    None => println!("Invalid trace value {}", value),
+                },
+                Some(("stdin", name)) => config.stdin = Some(name.to_string()),
+                Some(("onexit", value)) => match OnExit::from_name(value) {
+                    Some(on_exit) => config.on_exit = on_exit,
+                    None => println!("Invalid onexit value {}", value),
+                },
+                _ => println!("Ignoring unknown kernel option {}", word),
+            }
+        }
+        config
+    }
+}
+
+fn parse_switch(value: &str) -> Option<bool> {
+    match value {
+        "on" => Some(true),
+        "off" => Some(false),
+        _ => None,
+    }
+}
+
+// Parse the command line, needs the heap
+pub fn init(command_line: &str) {
+    CONFIG.call_once(|| Config::parse(command_line));
+}
+
+// The defaults until init is called
+pub fn config() -> &'static Config {
+    CONFIG.get().unwrap_or(&DEFAULT_CONFIG)
+}
+
+// println!() for kernel messages, only shown if the loglevel includes them
+#[macro_export]
+macro_rules! log {
+    ($level:ident, $($arg:tt)*) => {
+        if $crate::cmdline::config().loglevel >= $crate::cmdline::LogLevel::$level {
+            $crate::println!($($arg)*);
+        }
+    };
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test_case]
+    fn options_are_parsed() {
+        let config = Config::parse("autorun=dummy layout=us serial=off budget=1000 trace=on onexit=menu");
+        assert_eq!(config.autorun.as_deref(), Some("dummy"));
+        assert_eq!(config.layout, Layout::Us104);
+        assert!(!config.serial);
+        assert_eq!(config.budget, Some(1000));
+        assert!(config.trace);
+        assert_eq!(config.loglevel, LogLevel::Info);
+        assert_eq!(config.on_exit, OnExit::Menu);
+    }
+
+    #[test_case]
+    fn invalid_values_keep_the_default() {
+        let config = Config::parse("loglevel=loud budget=lots serial=maybe");
+        assert_eq!(config.loglevel, LogLevel::Info);
+        assert_eq!(config.budget, None);
+        assert!(config.serial);
+    }
+}
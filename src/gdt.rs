@@ -5,22 +5,28 @@ use lazy_static::lazy_static;
 
 pub const DOUBLE_FAULT_IST_INDEX: u16 = 0;
 
+const STACK_SIZE: usize = 4096 * 5;
+// A static array, so it exists before the heap and the page tables are set up
+static mut DOUBLE_FAULT_STACK: [u8; STACK_SIZE] = [0; STACK_SIZE];
+
 lazy_static! {
     static ref TSS: TaskStateSegment = {
         let mut tss = TaskStateSegment::new();
         tss.interrupt_stack_table[DOUBLE_FAULT_IST_INDEX as usize] = {
-            const STACK_SIZE: usize = 4096 * 5;
-            // For now we use an (unsafe) array for now, until we have memory management
-            static mut STACK: [u8; STACK_SIZE] = [0; STACK_SIZE];
-
-            let stack_start = VirtAddr::from_ptr(unsafe { &STACK });
-            let stack_end = stack_start + STACK_SIZE;
+            let (_, stack_end) = double_fault_stack_bounds();
             stack_end
         };
         tss
     };
 }
 
+// Start and end address of the stack the double fault handler runs on
+pub fn double_fault_stack_bounds() -> (VirtAddr, VirtAddr) {
+    let stack_start = VirtAddr::from_ptr(unsafe { &DOUBLE_FAULT_STACK });
+    let stack_end = stack_start + STACK_SIZE;
+    (stack_start, stack_end)
+}
+
 lazy_static! {
     static ref GDT: (GlobalDescriptorTable, Selectors) = {
         let mut gdt = GlobalDescriptorTable::new();
@@ -46,3 +52,20 @@ pub fn init() {
         load_tss(GDT.1.tss_selector);
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test_case]
+    fn code_segment_is_loaded() {
+        use x86_64::instructions::segmentation::cs;
+        assert_eq!(cs(), GDT.1.code_selector);
+    }
+
+    #[test_case]
+    fn double_fault_stack_is_in_tss() {
+        let (_, stack_end) = double_fault_stack_bounds();
+        assert_eq!(TSS.interrupt_stack_table[DOUBLE_FAULT_IST_INDEX as usize], stack_end);
+    }
+}
@@ -0,0 +1,184 @@
+// A 320x200 canvas with 256 palette colours that RCPU programs can draw on. It
+// is scaled up onto the framebuffer and hides the terminal while it is shown.
+
+use alloc::vec;
+use alloc::vec::Vec;
+use core::cmp::min;
+use spin::Mutex;
+use x86_64::instructions::interrupts;
+
+use crate::terminal::{FramebufferConsole, VGA_PALETTE, WRITER};
+
+pub const CANVAS_WIDTH: usize = 320;
+pub const CANVAS_HEIGHT: usize = 200;
+
+// The canvas while graphics mode is on. Lock order is CANVAS, then WRITER.
+static CANVAS: Mutex<Option<Canvas>> = Mutex::new(None);
+
+struct Canvas {
+    pixels: Vec<u8>,              // Palette indices, row by row
+    palette: [(u8, u8, u8); 256],
+    scale: usize,                 // Framebuffer pixels per canvas pixel
+    left: usize,                  // Where the canvas starts on the framebuffer
+    top: usize,
+}
+
+impl Canvas {
+    fn draw_pixel(&self, framebuffer: &mut FramebufferConsole, x: usize, y: usize) {
+        let color = self.palette[self.pixels[y * CANVAS_WIDTH + x] as usize];
+        framebuffer.fill_rect(self.left + x * self.scale, self.top + y * self.scale,
+                              self.scale, self.scale, color);
+    }
+}
+
+// Switch to graphics mode with a black canvas, false if there is no
+// framebuffer to draw on
+pub fn enter() -> bool {
+    interrupts::without_interrupts(|| {
+        let mut canvas = CANVAS.lock();
+        if canvas.is_some() {
+            return true;
+        }
+        let mut writer = WRITER.lock();
+        let framebuffer = match writer.framebuffer() {
+            Some(framebuffer) => framebuffer,
+            None => return false,
+        };
+        let width = framebuffer.pixel_width();
+        let height = framebuffer.pixel_height();
+        let scale = min(width / CANVAS_WIDTH, height / CANVAS_HEIGHT);
+        if scale == 0 {
+            return false;
+        }
+
+        framebuffer.hide_text();
+        framebuffer.fill_rect(0, 0, width, height, (0, 0, 0));
+        *canvas = Some(Canvas {
+            pixels: vec![0; CANVAS_WIDTH * CANVAS_HEIGHT],
+            palette: default_palette(),
+            scale,
+            left: (width - CANVAS_WIDTH * scale) / 2,
+            top: (height - CANVAS_HEIGHT * scale) / 2,
+        });
+        true
+    })
+}
+
+// Back to the terminal, it shows what was printed in the meantime
+pub fn leave() {
+    interrupts::without_interrupts(|| {
+        if CANVAS.lock().take().is_some() {
+            if let Some(framebuffer) = WRITER.lock().framebuffer() {
+                framebuffer.show_text();
+            }
+        }
+    });
+}
+
+// Run f with the canvas and the framebuffer, nothing happens in text mode
+fn with_canvas<F: FnOnce(&mut Canvas, &mut FramebufferConsole)>(f: F) {
+    interrupts::without_interrupts(|| {
+        let mut canvas = CANVAS.lock();
+        let mut writer = WRITER.lock();
+        if let (Some(canvas), Some(framebuffer)) = (canvas.as_mut(), writer.framebuffer()) {
+            f(canvas, framebuffer);
+        }
+    });
+}
+
+pub fn plot(x: usize, y: usize, color: u8) {
+    fill_rect(x, y, 1, 1, color);
+}
+
+pub fn fill_rect(x: usize, y: usize, width: usize, height: usize, color: u8) {
+    let (x, y, width, height) = match clip(x, y, width, height) {
+        Some(rect) => rect,
+        None => return,
+    };
+    with_canvas(|canvas, framebuffer| {
+        for row in y..y + height {
+            for pixel in &mut canvas.pixels[row * CANVAS_WIDTH + x..row * CANVAS_WIDTH + x + width] {
+                *pixel = color;
+            }
+        }
+        framebuffer.fill_rect(canvas.left + x * canvas.scale, canvas.top + y * canvas.scale,
+                              width * canvas.scale, height * canvas.scale,
+                              canvas.palette[color as usize]);
+    });
+}
+
+// Copy a width x height image to the canvas at x, y. Only the part on the
+// canvas is read, pixel gives the colour at a column and row of the image.
+pub fn blit<F: Fn(usize, usize) -> u8>(x: usize, y: usize, width: usize, height: usize, pixel: F) {
+    let (x, y, width, height) = match clip(x, y, width, height) {
+        Some(rect) => rect,
+        None => return,
+    };
+    with_canvas(|canvas, framebuffer| {
+        for row in 0..height {
+            for column in 0..width {
+                canvas.pixels[(y + row) * CANVAS_WIDTH + x + column] = pixel(column, row);
+                canvas.draw_pixel(framebuffer, x + column, y + row);
+            }
+        }
+    });
+}
+
+// Change a palette colour, the pixels that use it change with it
+pub fn set_palette(index: u8, rgb: (u8, u8, u8)) {
+    with_canvas(|canvas, framebuffer| {
+        canvas.palette[index as usize] = rgb;
+        for y in 0..CANVAS_HEIGHT {
+            for x in 0..CANVAS_WIDTH {
+                if canvas.pixels[y * CANVAS_WIDTH + x] == index {
+                    canvas.draw_pixel(framebuffer, x, y);
+                }
+            }
+        }
+    });
+}
+
+// The part of a rectangle that lies on the canvas, if any
+fn clip(x: usize, y: usize, width: usize, height: usize) -> Option<(usize, usize, usize, usize)> {
+    if x >= CANVAS_WIDTH || y >= CANVAS_HEIGHT || width == 0 || height == 0 {
+        return None;
+    }
+    Some((x, y, min(width, CANVAS_WIDTH - x), min(height, CANVAS_HEIGHT - y)))
+}
+
+// The 16 VGA colours, 16 shades of grey and a 6x6x6 colour cube, the last 8
+// entries are black
+fn default_palette() -> [(u8, u8, u8); 256] {
+    let mut palette = [(0, 0, 0); 256];
+    palette[..16].copy_from_slice(&VGA_PALETTE);
+    for grey in 0..16 {
+        let value = (grey * 17) as u8;
+        palette[16 + grey] = (value, value, value);
+    }
+    for index in 0..216 {
+        let level = |component: usize| (component * 51) as u8;
+        palette[32 + index] = (level(index / 36), level(index / 6 % 6), level(index % 6));
+    }
+    palette
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test_case]
+    fn rectangles_are_clipped_to_the_canvas() {
+        assert_eq!(clip(10, 20, 5, 5), Some((10, 20, 5, 5)));
+        assert_eq!(clip(318, 198, 5, 5), Some((318, 198, 2, 2)));
+        assert_eq!(clip(320, 0, 5, 5), None);
+        assert_eq!(clip(0, 0, 0, 5), None);
+    }
+
+    #[test_case]
+    fn default_palette_starts_with_the_vga_colours() {
+        let palette = default_palette();
+        assert_eq!(palette[15], (0xff, 0xff, 0xff));
+        assert_eq!(palette[31], (0xff, 0xff, 0xff));
+        assert_eq!(palette[32 + 215], (0xff, 0xff, 0xff));
+    }
+}
@@ -1,11 +1,17 @@
 use pic8259_simple::ChainedPics;
 use spin;
-use x86_64::structures::idt::{InterruptDescriptorTable, InterruptStackFrame};
+use x86_64::structures::idt::{InterruptDescriptorTable, InterruptStackFrame, PageFaultErrorCode};
 use lazy_static::lazy_static;
 
 use crate::println;
 use crate::gdt;
+use crate::memory::ProgramWindow;
+use crate::keyboard;
 use crate::keyboard::KEYBUFFER;
+use crate::mouse;
+use crate::serial::SERIAL1;
+use crate::timer;
+use crate::tty;
 
 pub const PIC_1_OFFSET: u8 = 32;
 pub const PIC_2_OFFSET: u8 = PIC_1_OFFSET + 8;
@@ -18,6 +24,8 @@ pub static PICS: spin::Mutex<ChainedPics> =
 pub enum InterruptIndex {
     Timer = PIC_1_OFFSET,
     Keyboard,
+    Serial1 = PIC_1_OFFSET + 4,
+    Mouse = PIC_2_OFFSET + 4,
 }
 
 impl InterruptIndex {
@@ -33,15 +41,37 @@ impl InterruptIndex {
 lazy_static! {
     static ref IDT: InterruptDescriptorTable = {
         let mut idt = InterruptDescriptorTable::new();
+        idt.divide_error.set_handler_fn(divide_error_handler);
+        idt.debug.set_handler_fn(debug_handler);
+        idt.non_maskable_interrupt.set_handler_fn(non_maskable_interrupt_handler);
         idt.breakpoint.set_handler_fn(breakpoint_handler);
+        idt.overflow.set_handler_fn(overflow_handler);
+        idt.bound_range_exceeded.set_handler_fn(bound_range_exceeded_handler);
+        idt.invalid_opcode.set_handler_fn(invalid_opcode_handler);
+        idt.device_not_available.set_handler_fn(device_not_available_handler);
         unsafe {
             idt.double_fault.set_handler_fn(double_fault_handler)
                 .set_stack_index(gdt::DOUBLE_FAULT_IST_INDEX);
         }
+        idt.invalid_tss.set_handler_fn(invalid_tss_handler);
+        idt.segment_not_present.set_handler_fn(segment_not_present_handler);
+        idt.stack_segment_fault.set_handler_fn(stack_segment_fault_handler);
+        idt.general_protection_fault.set_handler_fn(general_protection_fault_handler);
+        idt.page_fault.set_handler_fn(page_fault_handler);
+        idt.x87_floating_point.set_handler_fn(x87_floating_point_handler);
+        idt.alignment_check.set_handler_fn(alignment_check_handler);
+        idt.machine_check.set_handler_fn(machine_check_handler);
+        idt.simd_floating_point.set_handler_fn(simd_floating_point_handler);
+        idt.virtualization.set_handler_fn(virtualization_handler);
+        idt.security_exception.set_handler_fn(security_exception_handler);
         idt[InterruptIndex::Timer.as_usize()]
             .set_handler_fn(timer_interrupt_handler);
         idt[InterruptIndex::Keyboard.as_usize()]
             .set_handler_fn(keyboard_interrupt_handler);
+        idt[InterruptIndex::Serial1.as_usize()]
+            .set_handler_fn(serial_interrupt_handler);
+        idt[InterruptIndex::Mouse.as_usize()]
+            .set_handler_fn(mouse_interrupt_handler);
         idt
     };
 }
@@ -50,11 +80,46 @@ pub fn init_idt() {
     IDT.load()
 }
 
-extern "x86-interrupt" fn double_fault_handler (
-    stack_frame: &mut InterruptStackFrame,
-    _error_code: u64) -> !
+// Let an IRQ through the PICs, whatever the firmware masked stays masked.
+// IRQs 8 to 15 also need the cascade (IRQ 2) of the first PIC.
+pub fn unmask_irq(irq: u8) {
+    use x86_64::instructions::port::Port;
+
+    let (port, bit) = if irq < 8 { (0x21, irq) } else { (0xa1, irq - 8) };
+    let mut data_port: Port<u8> = Port::new(port);
+    unsafe {
+        let mask = data_port.read();
+        data_port.write(mask & !(1 << bit));
+    }
+}
+
+// Report an exception we can't recover from and stop
+fn fatal_exception(name: &str, vector: u8, error_code: Option<u64>,
+                   stack_frame: &InterruptStackFrame) -> ! {
+    println!("BANG, Hit {} (vector {})", name, vector);
+    if let Some(error_code) = error_code {
+        println!("Error code: {:#x}", error_code);
+    }
+    panic!("Interrupted RIP: {:?}\n{:#?}", stack_frame.instruction_pointer, stack_frame);
+}
+
+extern "x86-interrupt" fn divide_error_handler (
+    stack_frame: &mut InterruptStackFrame)
+{
+    fatal_exception("divide error", 0, None, stack_frame);
+}
+
+extern "x86-interrupt" fn debug_handler (
+    stack_frame: &mut InterruptStackFrame)
 {
-    panic!("BANG, Hit a double fault\n{:#?}", stack_frame);
+    println!("BANG, Hit debug exception (vector 1) at {:?}", stack_frame.instruction_pointer);
+}
+
+extern "x86-interrupt" fn non_maskable_interrupt_handler (
+    stack_frame: &mut InterruptStackFrame)
+{
+    println!("BANG, Hit non-maskable interrupt (vector 2) at {:?}",
+        stack_frame.instruction_pointer);
 }
 
 extern "x86-interrupt" fn breakpoint_handler (
@@ -63,9 +128,129 @@ extern "x86-interrupt" fn breakpoint_handler (
     println!("BANG, Hit breakpoint\n{:#?}", stack_frame);
 }
 
+extern "x86-interrupt" fn overflow_handler (
+    stack_frame: &mut InterruptStackFrame)
+{
+    fatal_exception("overflow", 4, None, stack_frame);
+}
+
+extern "x86-interrupt" fn bound_range_exceeded_handler (
+    stack_frame: &mut InterruptStackFrame)
+{
+    fatal_exception("bound range exceeded", 5, None, stack_frame);
+}
+
+extern "x86-interrupt" fn invalid_opcode_handler (
+    stack_frame: &mut InterruptStackFrame)
+{
+    fatal_exception("invalid opcode", 6, None, stack_frame);
+}
+
+extern "x86-interrupt" fn device_not_available_handler (
+    stack_frame: &mut InterruptStackFrame)
+{
+    fatal_exception("device not available", 7, None, stack_frame);
+}
+
+extern "x86-interrupt" fn double_fault_handler (
+    stack_frame: &mut InterruptStackFrame,
+    error_code: u64) -> !
+{
+    #[cfg(test)]
+    tests::check_expected_double_fault();
+
+    fatal_exception("double fault", 8, Some(error_code), stack_frame);
+}
+
+extern "x86-interrupt" fn invalid_tss_handler (
+    stack_frame: &mut InterruptStackFrame,
+    error_code: u64)
+{
+    fatal_exception("invalid TSS", 10, Some(error_code), stack_frame);
+}
+
+extern "x86-interrupt" fn segment_not_present_handler (
+    stack_frame: &mut InterruptStackFrame,
+    error_code: u64)
+{
+    fatal_exception("segment not present", 11, Some(error_code), stack_frame);
+}
+
+extern "x86-interrupt" fn stack_segment_fault_handler (
+    stack_frame: &mut InterruptStackFrame,
+    error_code: u64)
+{
+    fatal_exception("stack-segment fault", 12, Some(error_code), stack_frame);
+}
+
+extern "x86-interrupt" fn general_protection_fault_handler (
+    stack_frame: &mut InterruptStackFrame,
+    error_code: u64)
+{
+    fatal_exception("general protection fault", 13, Some(error_code), stack_frame);
+}
+
+extern "x86-interrupt" fn page_fault_handler (
+    stack_frame: &mut InterruptStackFrame,
+    error_code: PageFaultErrorCode)
+{
+    use x86_64::registers::control::Cr2;
+
+    let address = Cr2::read();
+    println!("Page fault at {:?}: present: {}, write: {}, user: {}",
+        address,
+        error_code.contains(PageFaultErrorCode::PROTECTION_VIOLATION),
+        error_code.contains(PageFaultErrorCode::CAUSED_BY_WRITE),
+        error_code.contains(PageFaultErrorCode::USER_MODE));
+    if ProgramWindow::is_program_address(address.as_u64()) {
+        println!("RCPU program accessed memory outside of its window");
+    }
+    fatal_exception("page fault", 14, Some(error_code.bits()), stack_frame);
+}
+
+extern "x86-interrupt" fn x87_floating_point_handler (
+    stack_frame: &mut InterruptStackFrame)
+{
+    fatal_exception("x87 floating point exception", 16, None, stack_frame);
+}
+
+extern "x86-interrupt" fn alignment_check_handler (
+    stack_frame: &mut InterruptStackFrame,
+    error_code: u64)
+{
+    fatal_exception("alignment check", 17, Some(error_code), stack_frame);
+}
+
+extern "x86-interrupt" fn machine_check_handler (
+    stack_frame: &mut InterruptStackFrame) -> !
+{
+    fatal_exception("machine check", 18, None, stack_frame);
+}
+
+extern "x86-interrupt" fn simd_floating_point_handler (
+    stack_frame: &mut InterruptStackFrame)
+{
+    fatal_exception("SIMD floating point exception", 19, None, stack_frame);
+}
+
+extern "x86-interrupt" fn virtualization_handler (
+    stack_frame: &mut InterruptStackFrame)
+{
+    fatal_exception("virtualization exception", 20, None, stack_frame);
+}
+
+extern "x86-interrupt" fn security_exception_handler (
+    stack_frame: &mut InterruptStackFrame,
+    error_code: u64)
+{
+    fatal_exception("security exception", 30, Some(error_code), stack_frame);
+}
+
 extern "x86-interrupt" fn timer_interrupt_handler (
     _stack_frame: &mut InterruptStackFrame)
 {
+    timer::tick();
+
     unsafe {
         PICS.lock()
             .notify_end_of_interrupt(InterruptIndex::Timer.as_u8());
@@ -75,25 +260,29 @@ extern "x86-interrupt" fn timer_interrupt_handler (
 extern "x86-interrupt" fn keyboard_interrupt_handler (
     _stack_frame: &mut InterruptStackFrame)
 {
-    use pc_keyboard::{layouts, HandleControl, Keyboard, ScancodeSet1};
+    use pc_keyboard::{HandleControl, Keyboard, ScancodeSet1};
     use spin::Mutex;
     use x86_64::instructions::port::Port;
 
     lazy_static! {
-        static ref KEYBOARD: Mutex<Keyboard<layouts::Azerty, ScancodeSet1>> =
-            Mutex::new(Keyboard::new(layouts::Azerty, ScancodeSet1,
-                HandleControl::Ignore)
+        static ref KEYBOARD: Mutex<Keyboard<keyboard::RuntimeLayout, ScancodeSet1>> =
+            Mutex::new(Keyboard::new(keyboard::RuntimeLayout, ScancodeSet1,
+                HandleControl::MapLettersToUnicode)
             );
     }
 
     let mut keyboard = KEYBOARD.lock();
-    let mut keybuffer = KEYBUFFER.lock();
     let mut port = Port::new(0x60);
     let scancode: u8 = unsafe { port.read() };
+    keyboard::SCANCODES.push(scancode);
 
     if let Ok(Some(key_event)) = keyboard.add_byte(scancode) {
-        if let Some(key) = keyboard.process_keyevent(key_event) {
-            keybuffer.push(key);
+        if !keyboard::handle_kernel_keys(&key_event) {
+            if let Some(key) = keyboard.process_keyevent(key_event) {
+                if !tty::handle_signal_key(key) {
+                    KEYBUFFER.push(key);
+                }
+            }
         }
     }
 
@@ -102,3 +291,88 @@ extern "x86-interrupt" fn keyboard_interrupt_handler (
             .notify_end_of_interrupt(InterruptIndex::Keyboard.as_u8());
     }
 }
+
+extern "x86-interrupt" fn serial_interrupt_handler (
+    _stack_frame: &mut InterruptStackFrame)
+{
+    use pc_keyboard::DecodedKey;
+
+    let byte = SERIAL1.lock().receive();
+
+    // Terminals send a carriage return for Enter and DEL for Backspace, Ctrl
+    // combinations arrive as control characters already
+    let key = match byte {
+        b'\r' => DecodedKey::Unicode('\n'),
+        0x7f => DecodedKey::Unicode('\u{8}'),
+        byte => DecodedKey::Unicode(byte as char),
+    };
+    if !tty::handle_signal_key(key) {
+        KEYBUFFER.push(key);
+    }
+
+    unsafe {
+        PICS.lock()
+            .notify_end_of_interrupt(InterruptIndex::Serial1.as_u8());
+    }
+}
+
+extern "x86-interrupt" fn mouse_interrupt_handler (
+    _stack_frame: &mut InterruptStackFrame)
+{
+    use x86_64::instructions::port::Port;
+
+    let mut port = Port::new(0x60);
+    let byte: u8 = unsafe { port.read() };
+    mouse::handle_byte(byte);
+
+    unsafe {
+        PICS.lock()
+            .notify_end_of_interrupt(InterruptIndex::Mouse.as_u8());
+    }
+}
+
+#[cfg(test)]
+pub mod tests {
+    use core::sync::atomic::{AtomicBool, Ordering};
+
+    use crate::{serial_print, serial_println};
+    use crate::gdt;
+    use crate::qemu::{exit_qemu, QemuExitCode};
+
+    static EXPECT_DOUBLE_FAULT: AtomicBool = AtomicBool::new(false);
+
+    #[test_case]
+    fn breakpoint_returns() {
+        x86_64::instructions::interrupts::int3();
+    }
+
+    // Called from the double fault handler, passes the test if the handler
+    // runs on its own stack
+    pub fn check_expected_double_fault() {
+        if !EXPECT_DOUBLE_FAULT.load(Ordering::SeqCst) {
+            return;
+        }
+        let marker = 0u8;
+        let stack_pointer = &marker as *const u8 as u64;
+        let (stack_start, stack_end) = gdt::double_fault_stack_bounds();
+        if stack_pointer >= stack_start.as_u64() && stack_pointer < stack_end.as_u64() {
+            serial_println!("[ok]");
+            exit_qemu(QemuExitCode::Success);
+        }
+        panic!("Double fault handler runs on the wrong stack");
+    }
+
+    #[allow(unconditional_recursion)]
+    fn stack_overflow() {
+        stack_overflow();
+        // Prevent tail call optimization
+        unsafe { core::ptr::read_volatile(&0u8) };
+    }
+
+    pub fn stack_overflow_uses_double_fault_stack() -> ! {
+        serial_print!("rcpu_os::interrupts::tests::stack_overflow_uses_double_fault_stack...\t");
+        EXPECT_DOUBLE_FAULT.store(true, Ordering::SeqCst);
+        stack_overflow();
+        panic!("Execution continued after stack overflow");
+    }
+}
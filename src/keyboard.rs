@@ -1,44 +1,210 @@
-use pc_keyboard::DecodedKey;
-use lazy_static::lazy_static;
-use spin::Mutex;
+use core::sync::atomic::{AtomicBool, AtomicU8, Ordering};
 
-use crate::println;
+use pc_keyboard::{layouts, DecodedKey, HandleControl, KeyCode, KeyEvent, KeyState,
+    KeyboardLayout, Modifiers};
+
+use crate::keyqueue::KeyQueue;
+use crate::status;
+use crate::tty;
+use crate::tty::InputMode;
+use crate::terminal::WRITER;
 
 const KEYBUFFER_SIZE: usize = 128;
+const SCANCODE_BUFFER_SIZE: usize = 128;
+// Shift+PageUp/PageDown scroll by half a screen
+const SCROLL_LINES: usize = 12;
+
+// The layouts of pc-keyboard, F12 goes to the next one
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Layout {
+    Azerty = 0,
+    Us104,
+    Uk105,
+    Dvorak104,
+    Jis109,
+}
+
+const LAYOUTS: [Layout; 5] = [
+    Layout::Azerty, Layout::Us104, Layout::Uk105, Layout::Dvorak104, Layout::Jis109,
+];
+
+impl Layout {
+    pub fn name(self) -> &'static str {
+        match self {
+            Layout::Azerty => "azerty",
+            Layout::Us104 => "us",
+            Layout::Uk105 => "uk",
+            Layout::Dvorak104 => "dvorak",
+            Layout::Jis109 => "jis",
+        }
+    }
+
+    pub fn from_name(name: &str) -> Option<Layout> {
+        LAYOUTS.iter().copied().find(|layout| layout.name() == name)
+    }
+
+    fn next(self) -> Layout {
+        LAYOUTS[(self as usize + 1) % LAYOUTS.len()]
+    }
+}
+
+static LAYOUT: AtomicU8 = AtomicU8::new(Layout::Azerty as u8);
+
+pub fn layout() -> Layout {
+    LAYOUTS[LAYOUT.load(Ordering::Relaxed) as usize]
+}
+
+pub fn set_layout(layout: Layout) {
+    LAYOUT.store(layout as u8, Ordering::Relaxed);
+}
+
+// A pc-keyboard layout that maps the keys with the layout selected right now
+pub struct RuntimeLayout;
+
+impl KeyboardLayout for RuntimeLayout {
+    fn map_keycode(keycode: KeyCode, modifiers: &Modifiers,
+                   handle_ctrl: HandleControl) -> DecodedKey {
+        match layout() {
+            Layout::Azerty => layouts::Azerty::map_keycode(keycode, modifiers, handle_ctrl),
+            Layout::Us104 => layouts::Us104Key::map_keycode(keycode, modifiers, handle_ctrl),
+            Layout::Uk105 => layouts::Uk105Key::map_keycode(keycode, modifiers, handle_ctrl),
+            Layout::Dvorak104 => layouts::Dvorak104Key::map_keycode(keycode, modifiers, handle_ctrl),
+            Layout::Jis109 => layouts::Jis109Key::map_keycode(keycode, modifiers, handle_ctrl),
+        }
+    }
+}
+
+static LEFT_SHIFT_HELD: AtomicBool = AtomicBool::new(false);
+static RIGHT_SHIFT_HELD: AtomicBool = AtomicBool::new(false);
 
-pub static KEYBUFFER: Mutex<KeyCodeIter> =
-    Mutex::new(KeyCodeIter {
-        buffer: [DecodedKey::Unicode('\0'); KEYBUFFER_SIZE],
-        head: 0,
-        tail: 0
-    });
+// Decoded keys from the keyboard and the serial port. Both are pushed from
+// interrupt handlers, which don't interrupt each other.
+pub static KEYBUFFER: KeyQueue<DecodedKey, KEYBUFFER_SIZE> =
+    KeyQueue::new([DecodedKey::Unicode('\0'); KEYBUFFER_SIZE]);
 
-#[derive(Clone, Debug)]
-pub struct KeyCodeIter {
-    buffer: [DecodedKey; KEYBUFFER_SIZE],
-    head: usize,
-    tail: usize,
+// Every byte the keyboard sends, presses and releases, for programs in raw mode
+pub static SCANCODES: KeyQueue<u8, SCANCODE_BUFFER_SIZE> =
+    KeyQueue::new([0; SCANCODE_BUFFER_SIZE]);
+
+// Wait for the next key, the CPU sleeps until an interrupt arrives. None if
+// Ctrl+C interrupted the wait.
+pub fn wait_for_key() -> Option<DecodedKey> {
+    wait_for(&KEYBUFFER)
+}
+
+// Wait for the next scancode, for programs in raw mode
+pub fn wait_for_scancode() -> Option<u8> {
+    wait_for(&SCANCODES)
+}
+
+// How many keys the queue of the input mode dropped since the last call. The
+// other queue is filled too but no one reads it, so its count is thrown away
+pub fn take_dropped(mode: InputMode) -> usize {
+    dropped_in(mode, &KEYBUFFER, &SCANCODES)
+}
+
+fn dropped_in<const N: usize, const M: usize>(mode: InputMode,
+                                              keys: &KeyQueue<DecodedKey, N>,
+                                              scancodes: &KeyQueue<u8, M>) -> usize {
+    let (keys, scancodes) = (keys.take_dropped(), scancodes.take_dropped());
+    match mode {
+        InputMode::Raw => scancodes,
+        InputMode::Cooked | InputMode::Cbreak => keys,
+    }
 }
 
-// TODO: self.tail and self.head can overflow
-impl Iterator for KeyCodeIter {
-    type Item = DecodedKey;
+fn wait_for<T: Copy, const N: usize>(queue: &KeyQueue<T, N>) -> Option<T> {
+    let mut waiting = false;
+    let value = loop {
+        tty::suspend_if_requested();
+        if tty::interrupt_pending() {
+            break None;
+        }
+        if let Some(value) = queue.pop() {
+            break Some(value);
+        }
+        if !waiting {
+            status::set_waiting_for_input(true);
+            waiting = true;
+        }
+        x86_64::instructions::interrupts::enable_and_hlt();
+    };
+    status::set_waiting_for_input(false);
+    value
+}
 
-    fn next(&mut self) -> Option<Self::Item> {
-        if self.head <= self.tail {
-            return None;
-        } else {
-            let ret = self.buffer[self.tail % KEYBUFFER_SIZE];
-            self.tail += 1;
-            self.head %= KEYBUFFER_SIZE;
-            return Some(ret);
+// Handles the keys the kernel uses itself, returns whether the event was used
+// up and shouldn't reach the program
+pub fn handle_kernel_keys(event: &KeyEvent) -> bool {
+    let shift_held = LEFT_SHIFT_HELD.load(Ordering::Relaxed) ||
+        RIGHT_SHIFT_HELD.load(Ordering::Relaxed);
+    match (event.code, event.state) {
+        (KeyCode::ShiftLeft, state) => {
+            LEFT_SHIFT_HELD.store(state == KeyState::Down, Ordering::Relaxed);
+            false
         }
+        (KeyCode::ShiftRight, state) => {
+            RIGHT_SHIFT_HELD.store(state == KeyState::Down, Ordering::Relaxed);
+            false
+        }
+        (KeyCode::PageUp, KeyState::Down) if shift_held => {
+            // Don't wait for the writer in an interrupt, just skip the scroll
+            if let Some(mut writer) = WRITER.try_lock() {
+                writer.scroll_up(SCROLL_LINES);
+            }
+            true
+        }
+        (KeyCode::PageDown, KeyState::Down) if shift_held => {
+            if let Some(mut writer) = WRITER.try_lock() {
+                writer.scroll_down(SCROLL_LINES);
+            }
+            true
+        }
+        // The status bar shows the new layout
+        (KeyCode::F12, KeyState::Down) => {
+            set_layout(layout().next());
+            true
+        }
+        (KeyCode::F12, KeyState::Up) => true,
+        _ => false,
     }
 }
 
-impl KeyCodeIter {
-    pub fn push(&mut self, key: DecodedKey) {
-        self.buffer[self.head % KEYBUFFER_SIZE] = key;
-        self.head += 1;
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test_case]
+    fn keys_come_out_in_order() {
+        let keys: KeyQueue<DecodedKey, 4> = KeyQueue::new([DecodedKey::Unicode('\0'); 4]);
+        keys.push(DecodedKey::Unicode('a'));
+        keys.push(DecodedKey::RawKey(KeyCode::F1));
+        assert_eq!(wait_for(&keys), Some(DecodedKey::Unicode('a')));
+        assert_eq!(wait_for(&keys), Some(DecodedKey::RawKey(KeyCode::F1)));
+        assert_eq!(keys.pop(), None);
+    }
+
+    #[test_case]
+    fn unread_scancodes_are_not_dropped_keys() {
+        let keys: KeyQueue<DecodedKey, 4> = KeyQueue::new([DecodedKey::Unicode('\0'); 4]);
+        let scancodes: KeyQueue<u8, 4> = KeyQueue::new([0; 4]);
+        for scancode in 0..6 {
+            scancodes.push(scancode);
+        }
+        keys.push(DecodedKey::Unicode('a'));
+        assert_eq!(dropped_in(InputMode::Cooked, &keys, &scancodes), 0);
+        // The count of the unread queue is gone as well
+        assert_eq!(dropped_in(InputMode::Raw, &keys, &scancodes), 0);
+        for _ in 0..5 {
+            keys.push(DecodedKey::Unicode('b'));
+        }
+        assert_eq!(dropped_in(InputMode::Cooked, &keys, &scancodes), 2);
+    }
+
+    #[test_case]
+    fn layouts_are_found_by_name() {
+        assert_eq!(Layout::from_name("dvorak"), Some(Layout::Dvorak104));
+        assert_eq!(Layout::from_name("qwertz"), None);
+        assert_eq!(Layout::Jis109.next(), Layout::Azerty);
     }
 }
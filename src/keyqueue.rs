@@ -0,0 +1,132 @@
+// A lock-free queue between one producer and one consumer. The interrupt
+// handlers push and the kernel or an RCPU program pops, so a handler never
+// waits for a lock the interrupted code holds.
+
+use core::cell::UnsafeCell;
+use core::sync::atomic::{AtomicUsize, Ordering};
+
+pub struct KeyQueue<T, const N: usize> {
+    buffer: UnsafeCell<[T; N]>,
+    head: AtomicUsize,     // Only written by push, counts up and wraps
+    tail: AtomicUsize,     // Only written by pop and clear
+    dropped: AtomicUsize,  // Pushes that didn't fit
+}
+
+// Only one side writes head and only the other writes tail, a slot is handed
+// over by the store of the index that covers it
+unsafe impl<T: Send, const N: usize> Sync for KeyQueue<T, N> {}
+
+impl<T, const N: usize> KeyQueue<T, N> {
+    // The indices wrap around usize::MAX, so head % N only stays in step when
+    // N divides the number of usize values
+    const SIZE_IS_POWER_OF_TWO: () = assert!(N.is_power_of_two(), "the size must be a power of two");
+
+    // The contents of buffer are never read, they only fill the slots
+    pub const fn new(buffer: [T; N]) -> KeyQueue<T, N> {
+        let () = Self::SIZE_IS_POWER_OF_TWO;
+        KeyQueue {
+            buffer: UnsafeCell::new(buffer),
+            head: AtomicUsize::new(0),
+            tail: AtomicUsize::new(0),
+            dropped: AtomicUsize::new(0),
+        }
+    }
+}
+
+impl<T: Copy, const N: usize> KeyQueue<T, N> {
+    // Producer side. When the queue is full the new value is dropped and
+    // counted, the keys that are waiting stay as they were typed.
+    pub fn push(&self, value: T) -> bool {
+        let head = self.head.load(Ordering::Relaxed);
+        let tail = self.tail.load(Ordering::Acquire);
+        if head.wrapping_sub(tail) >= N {
+            self.dropped.fetch_add(1, Ordering::Relaxed);
+            return false;
+        }
+        unsafe { (*self.buffer.get())[head % N] = value };
+        self.head.store(head.wrapping_add(1), Ordering::Release);
+        true
+    }
+
+    // Consumer side, the oldest value without removing it
+    pub fn peek(&self) -> Option<T> {
+        let tail = self.tail.load(Ordering::Relaxed);
+        let head = self.head.load(Ordering::Acquire);
+        if head == tail {
+            return None;
+        }
+        Some(unsafe { (*self.buffer.get())[tail % N] })
+    }
+
+    // Consumer side, returns immediately
+    pub fn pop(&self) -> Option<T> {
+        let value = self.peek()?;
+        let tail = self.tail.load(Ordering::Relaxed);
+        self.tail.store(tail.wrapping_add(1), Ordering::Release);
+        Some(value)
+    }
+
+    // Consumer side, the CPU sleeps until an interrupt pushes something
+    pub fn pop_blocking(&self) -> T {
+        loop {
+            if let Some(value) = self.pop() {
+                return value;
+            }
+            x86_64::instructions::interrupts::enable_and_hlt();
+        }
+    }
+
+    // Consumer side, throw away everything that is waiting
+    pub fn clear(&self) {
+        let head = self.head.load(Ordering::Acquire);
+        self.tail.store(head, Ordering::Release);
+    }
+
+    // How many values were dropped because the queue was full, since the
+    // last call
+    pub fn take_dropped(&self) -> usize {
+        self.dropped.swap(0, Ordering::Relaxed)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test_case]
+    fn values_come_out_in_order() {
+        let queue: KeyQueue<u8, 4> = KeyQueue::new([0; 4]);
+        assert_eq!(queue.pop(), None);
+        queue.push(1);
+        queue.push(2);
+        assert_eq!(queue.peek(), Some(1));
+        assert_eq!(queue.pop(), Some(1));
+        assert_eq!(queue.pop(), Some(2));
+        assert_eq!(queue.pop(), None);
+    }
+
+    #[test_case]
+    fn newest_values_are_dropped_when_full() {
+        let queue: KeyQueue<u8, 4> = KeyQueue::new([0; 4]);
+        for value in 0..6 {
+            queue.push(value);
+        }
+        assert_eq!(queue.take_dropped(), 2);
+        assert_eq!(queue.take_dropped(), 0);
+        assert_eq!(queue.pop(), Some(0));
+        queue.clear();
+        assert_eq!(queue.pop(), None);
+    }
+
+    #[test_case]
+    fn indices_can_wrap() {
+        let queue: KeyQueue<u8, 4> = KeyQueue::new([0; 4]);
+        queue.head.store(usize::MAX, Ordering::Relaxed);
+        queue.tail.store(usize::MAX, Ordering::Relaxed);
+        queue.push(1);
+        queue.push(2);
+        assert_eq!(queue.pop(), Some(1));
+        assert_eq!(queue.pop(), Some(2));
+        assert_eq!(queue.pop(), None);
+    }
+}
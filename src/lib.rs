@@ -1,7 +1,14 @@
 #![feature(lang_items)]
 #![feature(abi_x86_interrupt)]
+#![feature(alloc_error_handler)]
+#![feature(custom_test_frameworks)]
+#![test_runner(crate::test_runner)]
+#![reexport_test_harness_main = "test_main"]
+#![cfg_attr(test, no_main)]
 #![no_std]
 
+extern crate alloc;
+
 extern crate multiboot2;
 extern crate x86_64;
 extern crate volatile;
@@ -9,21 +16,32 @@ extern crate spin;
 extern crate lazy_static;
 extern crate pic8259_simple;
 extern crate pc_keyboard;
+extern crate linked_list_allocator;
+extern crate uart_16550;
 
 mod terminal;
+mod cmdline;
 mod interrupts;
 mod gdt;
 mod rcpu;
 mod memory;
 mod keyboard;
+mod keyqueue;
+mod mouse;
+mod allocator;
+mod serial;
+mod qemu;
+mod graphics;
+mod tty;
+mod timer;
+mod status;
 
+use alloc::vec::Vec;
 use core::panic::PanicInfo;
-use core::convert::TryInto;
-use core::ops::DerefMut;
 
+use multiboot2::ModuleTag;
 use pc_keyboard::{DecodedKey, KeyCode};
 
-use keyboard::KEYBUFFER;
 use terminal::WRITER;
 
 #[no_mangle]
@@ -32,98 +50,195 @@ pub extern fn rust_main(multiboot_information_address: usize) {
 
     let boot_info = unsafe{ multiboot2::load(multiboot_information_address) };
 
-    // RCPU memory starts right after the multiboot structure
-    let rcpu_mem_start = boot_info.end_address();
-
-    // To find the end, find the memory area containing the start address
-    let memory_map_tag = boot_info.memory_map_tag()
-                .expect("Memory map tag required");
-    let mut rcpu_mem_end: usize = rcpu_mem_start;
-    for memory_area in memory_map_tag.memory_areas() {
-        if memory_area.start_address() <= rcpu_mem_start.try_into().unwrap() &&
-            memory_area.end_address() >= rcpu_mem_start.try_into().unwrap() {
-                rcpu_mem_end = memory_area.end_address().try_into().unwrap();
+    memory::init(&boot_info);
+    allocator::init_heap().expect("Heap initialization failed");
+    WRITER.lock().use_framebuffer(&boot_info);
+    WRITER.lock().enable_scrollback();
+    let command_line = boot_info.command_line_tag().map_or("", |tag| tag.command_line());
+    cmdline::init(command_line);
+    log!(Debug, "Kernel command line: {}", command_line);
+    log!(Debug, "PS/2 mouse {}", if mouse::is_present() { "found" } else { "not found" });
+    keyboard::set_layout(cmdline::config().layout);
+    serial::set_mirror(cmdline::config().serial);
+    let stdin_module = cmdline::config().stdin.as_deref();
+
+    // The tests exit QEMU when they are done
+    #[cfg(test)]
+    test_main();
+
+    status::enable();
+
+    // Modules ending in .in are stdin for the programs, not programs
+    let (inputs, modules): (Vec<&ModuleTag>, Vec<&ModuleTag>) = boot_info.module_tags()
+        .partition(|module| rcpu::is_input_module(module.name()));
+
+    // Run every program without the menu and report to QEMU, the host
+    // compares the serial output with the golden files
+    if cfg!(feature = "golden-test") {
+        for module in &modules {
+            run_program(module, find_input(module, &inputs, stdin_module));
+        }
+        qemu::exit_qemu(qemu::QemuExitCode::Success);
+    }
+
+    let mut autorun = autorun_program(&modules);
+    loop {
+        let module = match autorun.take() {
+            Some(module) => module,
+            None => modules[select_program(&modules)],
+        };
+        run_program(module, find_input(module, &inputs, stdin_module));
+        match cmdline::config().on_exit {
+            cmdline::OnExit::Halt => hlt_loop(),
+            cmdline::OnExit::Menu => println!(),
+            cmdline::OnExit::PowerOff => qemu::power_off(),
+        }
+    }
+}
+
+// The program named by the autorun option, or the only one there is
+fn autorun_program<'a>(modules: &[&'a ModuleTag]) -> Option<&'a ModuleTag> {
+    match &cmdline::config().autorun {
+        Some(name) => {
+            let found = modules.iter().find(|module| rcpu::program_name(module.name()) == name);
+            if found.is_none() {
+                log!(Warn, "No program {} to autorun", name);
             }
+            found.copied()
+        }
+        None if modules.len() == 1 => Some(modules[0]),
+        None => None,
     }
+}
+
+// The module named on the kernel command line, otherwise <program>.in
+fn find_input<'a>(program: &ModuleTag, inputs: &[&'a ModuleTag],
+                  stdin_module: Option<&str>) -> Option<&'a ModuleTag> {
+    let program_name = rcpu::program_name(program.name());
+    let found = inputs.iter().find(|input| {
+        let input_name = rcpu::program_name(input.name());
+        match stdin_module {
+            Some(name) => input_name == name,
+            None => input_name.strip_suffix(".in") == Some(program_name),
+        }
+    });
+    if found.is_none() {
+        if let Some(name) = stdin_module {
+            log!(Warn, "No stdin module {}", name);
+        }
+    }
+    found.copied()
+}
 
-    // Show all modules
-    println!("Available programs");
-    let mut module_iter = boot_info.module_tags();
-    for module in module_iter {
-        println!(" {}", module.name());
+// Show all modules and let the user pick one with the arrow keys
+fn select_program(modules: &[&ModuleTag]) -> usize {
+    println!("Available programs (F12 changes the keyboard layout)");
+    for module in modules {
+        let name = rcpu::program_name(module.name());
+        match rcpu::program_title(module) {
+            Some(title) => println!(" {} - {}", name, title),
+            None => println!(" {}", name),
+        }
     }
 
-    // Show the selection cursor
-    let num_programs = boot_info.module_tags().count();
+    // Show the selection cursor, the list ends on the bottom row
+    let num_programs = modules.len();
+    let first_row = WRITER.lock().height() - 1 - num_programs;
     let mut selected_program_index = 0;
-    let mut selecting = true;
     {
         let mut writer = WRITER.lock();
-        writer.put_char_at('>', 24-num_programs, 0);
+        writer.put_char_at('>', first_row, 0);
     }
-    while selecting {
-        x86_64::instructions::interrupts::without_interrupts(|| {
-            let mut keybuffer = KEYBUFFER.lock();
-            for key in keybuffer.deref_mut() {
-                // Clear the old one
-                {
-                    let mut writer = WRITER.lock();
-                    writer.put_char_at(' ', 24-num_programs+selected_program_index, 0);
-                }
+    loop {
+        let key = keyboard::KEYBUFFER.pop_blocking();
+        // Clear the old one
+        {
+            let mut writer = WRITER.lock();
+            writer.put_char_at(' ', first_row+selected_program_index, 0);
+        }
 
-                
-                // Find the offset
-                match key {
-                    DecodedKey::RawKey(KeyCode::ArrowUp) => {
-                        if selected_program_index > 0 {
-                            selected_program_index -= 1;
-                        }
-                    }
-                    DecodedKey::RawKey(KeyCode::ArrowDown) => {
-                        if selected_program_index < num_programs-1 {
-                            selected_program_index += 1;
-                        }
-                    }
-                    DecodedKey::Unicode('\n') => {selecting = false; break}
-                    _ => (),
+        // Find the offset
+        match key {
+            DecodedKey::RawKey(KeyCode::ArrowUp) => {
+                if selected_program_index > 0 {
+                    selected_program_index -= 1;
                 }
-                selected_program_index %= num_programs;
-
-                // Print the next one
-                {
-                    let mut writer = WRITER.lock();
-                    writer.put_char_at('>', 24-num_programs+selected_program_index, 0);
+            }
+            DecodedKey::RawKey(KeyCode::ArrowDown) => {
+                if selected_program_index < num_programs-1 {
+                    selected_program_index += 1;
                 }
             }
-        });
-        x86_64::instructions::hlt();
+            DecodedKey::Unicode('\n') => break,
+            _ => (),
+        }
+
+        // Print the next one
+        {
+            let mut writer = WRITER.lock();
+            writer.put_char_at('>', first_row+selected_program_index, 0);
+        }
     }
+    selected_program_index
+}
 
-    let mut running_program = rcpu::RCPUProgram::from_module_tag(
-        boot_info.module_tags().nth(selected_program_index).expect("Unreachable statement"),
-        rcpu_mem_start,
-        rcpu_mem_end
-    );
+fn run_program(module: &ModuleTag, input: Option<&ModuleTag>) {
+    let mut running_program = match rcpu::RCPUProgram::from_module_tag(module) {
+        Ok(program) => program,
+        Err(error) => {
+            println!("Can't run {}: {}", rcpu::program_name(module.name()), error);
+            return;
+        }
+    };
+    if let Some(input) = input {
+        running_program.set_stdin(input);
+    }
+    status::set_program(Some(rcpu::program_name(module.name())));
+    tty::enable_signals(true);
+    // Keys dropped before the program started weren't meant for it
+    running_program.take_dropped_keys();
 
     while running_program.running {
         running_program.step()
     }
+    tty::enable_signals(false);
+    let dropped = running_program.take_dropped_keys();
+    if dropped > 0 {
+        log!(Warn, "{} keys were dropped, the key buffer was full", dropped);
+    }
+    // Show the terminal again if the program was drawing
+    graphics::leave();
+    status::set_program(None);
     println!("\nDone, thank you for flying RCPU_OS");
-    hlt_loop();
 }
 
 pub fn init() {
+    serial::init();
+    WRITER.lock().enable_cursor();
     gdt::init();
     interrupts::init_idt();
+    timer::init();
     unsafe { interrupts::PICS.lock().initialize() };
+    mouse::init();
     x86_64::instructions::interrupts::enable();
 
 }
 
+#[alloc_error_handler]
+fn alloc_error_handler(layout: alloc::alloc::Layout) -> ! {
+    panic!("Allocation error: {:?}", layout)
+}
+
 /// This function is called on panic.
 #[panic_handler]
 fn panic(info: &PanicInfo) -> ! {
+    if cfg!(test) {
+        serial_println!("[failed]");
+    }
     println!("{}", info);
+    if cfg!(feature = "golden-test") || cfg!(test) {
+        qemu::exit_qemu(qemu::QemuExitCode::Failed);
+    }
     hlt_loop();
 }
 
@@ -133,3 +248,28 @@ pub fn hlt_loop() -> ! {
     }
 }
 
+
+#[cfg(test)]
+pub trait Testable {
+    fn run(&self);
+}
+
+#[cfg(test)]
+impl<T: Fn()> Testable for T {
+    fn run(&self) {
+        serial_print!("{}...\t", core::any::type_name::<T>());
+        self();
+        serial_println!("[ok]");
+    }
+}
+
+// Runs all #[test_case] functions in QEMU, see tests/run-kernel-tests.sh
+#[cfg(test)]
+fn test_runner(tests: &[&dyn Testable]) {
+    serial_println!("Running {} tests", tests.len() + 1);
+    for test in tests {
+        test.run();
+    }
+    // This one can't return, it exits QEMU from the double fault handler
+    interrupts::tests::stack_overflow_uses_double_fault_stack();
+}
@@ -1,3 +1,323 @@
+use alloc::vec::Vec;
+use core::sync::atomic::{AtomicU64, Ordering};
+
+use spin::Mutex;
+use x86_64::{PhysAddr, VirtAddr};
+use x86_64::registers::control::{Cr0, Cr0Flags, Cr3};
+use x86_64::registers::model_specific::{Efer, EferFlags};
+use x86_64::structures::paging::{FrameAllocator, Mapper, OffsetPageTable, Page, PageTable,
+    PageTableFlags, PhysFrame, Size2MiB, Size4KiB};
+use x86_64::structures::paging::mapper::MapToError;
+use multiboot2::{BootInformation, ElfSectionFlags, ElfSectionsTag};
+
+// boot.asm only identity maps the first GiB, frames above it can't be used as
+// page tables before the kernel is remapped. The new page tables map all
+// memory, so the limit is lifted after the switch.
+const IDENTITY_MAPPED_END: u64 = 0x4000_0000;
+const MAX_MEMORY_REGIONS: usize = 32;
+const FRAME_SIZE: u64 = 4096;
+const HUGE_PAGE_SIZE: u64 = 0x20_0000;
+
+// Every RCPU program gets its own window: its RAM and its stack, each
+// surrounded by unmapped guard pages so an escape page faults
+pub const PROGRAM_WINDOWS_START: u64 = 0x_5555_0000_0000;
+pub const PROGRAM_WINDOW_SIZE: u64 = 0x10_0000; // 1 MiB
+const MAX_PROGRAM_WINDOWS: u64 = 512;
+pub const PROGRAM_AREA_SIZE: usize = 65536 * 2; // 64K words
+
+static NEXT_PROGRAM_WINDOW: AtomicU64 = AtomicU64::new(0);
+
+extern "C" {
+    // Defined in boot.asm, left unmapped so a kernel stack overflow faults
+    static stack_guard_page: u8;
+}
+
+// Physical memory is identity mapped, so the offset is 0
+pub static MAPPER: Mutex<Option<OffsetPageTable<'static>>> = Mutex::new(None);
+pub static FRAME_ALLOCATOR: Mutex<Option<BootInfoFrameAllocator>> = Mutex::new(None);
+
+/// Set up the frame allocator and switch to the kernel's own page tables
+pub fn init(boot_info: &BootInformation) {
+    let memory_map_tag = boot_info.memory_map_tag()
+                .expect("Memory map tag required");
+    let elf_sections_tag = boot_info.elf_sections_tag()
+                .expect("Elf sections tag required");
+
+    // Don't hand out the kernel, the multiboot structure or any of the modules
+    let (_, mut first_free) = kernel_bounds(&elf_sections_tag);
+    for module in boot_info.module_tags() {
+        if u64::from(module.end_address()) > first_free {
+            first_free = module.end_address().into();
+        }
+    }
+    let boot_info_end = boot_info.end_address() as u64;
+    if boot_info_end > first_free {
+        first_free = boot_info_end;
+    }
+
+    let mut allocator = BootInfoFrameAllocator {
+        regions: [(0, 0); MAX_MEMORY_REGIONS],
+        num_regions: 0,
+        next: align_up(first_free, FRAME_SIZE),
+        limit: IDENTITY_MAPPED_END,
+        free_frames: Vec::new(),
+    };
+    let mut memory_end = 0;
+    for memory_area in memory_map_tag.memory_areas() {
+        if memory_area.end_address() > memory_end {
+            memory_end = memory_area.end_address();
+        }
+        if allocator.num_regions == MAX_MEMORY_REGIONS {
+            continue;
+        }
+        allocator.regions[allocator.num_regions] =
+            (memory_area.start_address(), memory_area.end_address());
+        allocator.num_regions += 1;
+    }
+
+    let mapper = remap_kernel(&elf_sections_tag, memory_end, &mut allocator);
+    allocator.limit = u64::MAX;
+
+    *MAPPER.lock() = Some(mapper);
+    *FRAME_ALLOCATOR.lock() = Some(allocator);
+}
+
+// Lowest start and highest end address of the loaded kernel sections
+fn kernel_bounds(elf_sections_tag: &ElfSectionsTag) -> (u64, u64) {
+    let mut start = u64::MAX;
+    let mut end = 0;
+    for section in elf_sections_tag.sections() {
+        if !section.is_allocated() {
+            continue;
+        }
+        if section.start_address() < start {
+            start = section.start_address();
+        }
+        if section.end_address() > end {
+            end = section.end_address();
+        }
+    }
+    (start, end)
+}
+
+// Flags for an identity mapped page, pages of the kernel get the permissions of
+// their ELF sections, everything else is writable data
+fn identity_page_flags(elf_sections_tag: &ElfSectionsTag, address: u64) -> PageTableFlags {
+    let mut in_kernel = false;
+    let mut writable = false;
+    let mut executable = false;
+    for section in elf_sections_tag.sections() {
+        if section.is_allocated() &&
+            section.start_address() < address + FRAME_SIZE &&
+            section.end_address() > address {
+                in_kernel = true;
+                writable |= section.flags().contains(ElfSectionFlags::WRITABLE);
+                executable |= section.flags().contains(ElfSectionFlags::EXECUTABLE);
+            }
+    }
+
+    let mut flags = PageTableFlags::PRESENT;
+    if !in_kernel || writable {
+        flags |= PageTableFlags::WRITABLE;
+    }
+    if !in_kernel || !executable {
+        flags |= PageTableFlags::NO_EXECUTE;
+    }
+    flags
+}
+
+// Build new page tables that identity map all physical memory, with the kernel
+// text read-only and all data non-executable, and switch to them. The page at
+// address 0 and the boot stack guard page are left unmapped.
+fn remap_kernel(elf_sections_tag: &ElfSectionsTag, memory_end: u64,
+                allocator: &mut BootInfoFrameAllocator) -> OffsetPageTable<'static> {
+    let (kernel_start, kernel_end) = kernel_bounds(elf_sections_tag);
+    let guard_page = unsafe { &stack_guard_page as *const u8 as u64 };
+
+    let new_table_frame: PhysFrame = allocator.allocate_frame()
+                .expect("Out of memory");
+    let new_table = unsafe {
+        &mut *(new_table_frame.start_address().as_u64() as *mut PageTable)
+    };
+    new_table.zero();
+    let mut new_mapper = unsafe { OffsetPageTable::new(new_table, VirtAddr::new(0)) };
+
+    let mut address = 0;
+    while address < memory_end {
+        let chunk_end = address + HUGE_PAGE_SIZE;
+        if address == 0 || (address < kernel_end && chunk_end > kernel_start) {
+            // Map this part page by page so every page gets its own flags
+            for page_address in (address..chunk_end).step_by(FRAME_SIZE as usize) {
+                if page_address == 0 || page_address == guard_page {
+                    continue;
+                }
+                let page = Page::<Size4KiB>::containing_address(VirtAddr::new(page_address));
+                let frame = PhysFrame::containing_address(PhysAddr::new(page_address));
+                let flags = identity_page_flags(elf_sections_tag, page_address);
+                unsafe {
+                    new_mapper.map_to(page, frame, flags, allocator)
+                        .expect("Failed to remap the kernel")
+                        .ignore();
+                }
+            }
+        } else {
+            let page = Page::<Size2MiB>::containing_address(VirtAddr::new(address));
+            let frame = PhysFrame::containing_address(PhysAddr::new(address));
+            let flags = PageTableFlags::PRESENT | PageTableFlags::WRITABLE |
+                PageTableFlags::NO_EXECUTE;
+            unsafe {
+                new_mapper.map_to(page, frame, flags, allocator)
+                    .expect("Failed to remap physical memory")
+                    .ignore();
+            }
+        }
+        address = chunk_end;
+    }
+
+    unsafe {
+        // NX bits are reserved until this is enabled
+        Efer::update(|flags| *flags |= EferFlags::NO_EXECUTE_ENABLE);
+        // Make read-only pages read-only for the kernel too
+        Cr0::update(|flags| *flags |= Cr0Flags::WRITE_PROTECT);
+        let (_, cr3_flags) = Cr3::read();
+        Cr3::write(new_table_frame, cr3_flags);
+    }
+
+    new_mapper
+}
+
+// Hands out the usable frames from the multiboot memory map one by one, frames
+// that are given back are reused first
+pub struct BootInfoFrameAllocator {
+    regions: [(u64, u64); MAX_MEMORY_REGIONS],
+    num_regions: usize,
+    next: u64,
+    limit: u64,  // No frames at or above this address
+    free_frames: Vec<PhysFrame>,
+}
+
+impl BootInfoFrameAllocator {
+    pub fn deallocate_frame(&mut self, frame: PhysFrame) {
+        self.free_frames.push(frame);
+    }
+}
+
+unsafe impl FrameAllocator<Size4KiB> for BootInfoFrameAllocator {
+    fn allocate_frame(&mut self) -> Option<PhysFrame> {
+        if let Some(frame) = self.free_frames.pop() {
+            return Some(frame);
+        }
+        for &(start, end) in &self.regions[..self.num_regions] {
+            let candidate = align_up(if start > self.next { start } else { self.next }, FRAME_SIZE);
+            if candidate + FRAME_SIZE <= end.min(self.limit) {
+                self.next = candidate + FRAME_SIZE;
+                return Some(PhysFrame::containing_address(PhysAddr::new(candidate)));
+            }
+        }
+        None
+    }
+}
+
+// The memory of a single RCPU program, unmapped again when dropped
+#[derive(Debug)]
+pub struct ProgramWindow {
+    pub ram: *mut u16,
+    pub stack: *mut u16,
+}
+
+impl ProgramWindow {
+    pub fn allocate() -> ProgramWindow {
+        let index = NEXT_PROGRAM_WINDOW.fetch_add(1, Ordering::Relaxed) % MAX_PROGRAM_WINDOWS;
+        let window_start = PROGRAM_WINDOWS_START + index * PROGRAM_WINDOW_SIZE;
+
+        // guard page, RAM, guard page, stack, guard page
+        let ram = window_start + FRAME_SIZE;
+        let stack = ram + PROGRAM_AREA_SIZE as u64 + FRAME_SIZE;
+        map_zeroed(ram, PROGRAM_AREA_SIZE as u64);
+        map_zeroed(stack, PROGRAM_AREA_SIZE as u64);
+
+        ProgramWindow {
+            ram: ram as *mut u16,
+            stack: stack as *mut u16,
+        }
+    }
+
+    /// Whether an address lies in the virtual memory reserved for RCPU programs
+    pub fn is_program_address(address: u64) -> bool {
+        address >= PROGRAM_WINDOWS_START &&
+            address < PROGRAM_WINDOWS_START + MAX_PROGRAM_WINDOWS * PROGRAM_WINDOW_SIZE
+    }
+}
+
+impl Drop for ProgramWindow {
+    fn drop(&mut self) {
+        unmap(self.ram as u64, PROGRAM_AREA_SIZE as u64);
+        unmap(self.stack as u64, PROGRAM_AREA_SIZE as u64);
+    }
+}
+
+fn map_zeroed(start: u64, size: u64) {
+    let mut mapper = MAPPER.lock();
+    let mapper = mapper.as_mut().expect("Memory not initialized");
+    let mut frame_allocator = FRAME_ALLOCATOR.lock();
+    let frame_allocator = frame_allocator.as_mut().expect("Memory not initialized");
+
+    let start_page = Page::<Size4KiB>::containing_address(VirtAddr::new(start));
+    let end_page = Page::<Size4KiB>::containing_address(VirtAddr::new(start + size));
+    for page in Page::range(start_page, end_page) {
+        let frame = frame_allocator.allocate_frame().expect("Out of memory");
+        let flags = PageTableFlags::PRESENT | PageTableFlags::WRITABLE |
+            PageTableFlags::NO_EXECUTE;
+        unsafe {
+            mapper.map_to(page, frame, flags, frame_allocator)
+                .expect("Failed to map program memory")
+                .flush();
+            core::ptr::write_bytes(page.start_address().as_mut_ptr::<u8>(), 0, FRAME_SIZE as usize);
+        }
+    }
+}
+
+/// Identity map device memory that lies outside of the RAM, like the framebuffer
+pub fn identity_map(start: u64, size: u64) {
+    let mut mapper = MAPPER.lock();
+    let mapper = mapper.as_mut().expect("Memory not initialized");
+    let mut frame_allocator = FRAME_ALLOCATOR.lock();
+    let frame_allocator = frame_allocator.as_mut().expect("Memory not initialized");
+
+    let start_page = Page::<Size4KiB>::containing_address(VirtAddr::new(start));
+    let end_page = Page::<Size4KiB>::containing_address(VirtAddr::new(start + size - 1));
+    for page in Page::range_inclusive(start_page, end_page) {
+        let frame = PhysFrame::containing_address(PhysAddr::new(page.start_address().as_u64()));
+        let flags = PageTableFlags::PRESENT | PageTableFlags::WRITABLE |
+            PageTableFlags::NO_EXECUTE;
+        match unsafe { mapper.map_to(page, frame, flags, frame_allocator) } {
+            Ok(flush) => flush.flush(),
+            // Memory in the RAM range is identity mapped already
+            Err(MapToError::PageAlreadyMapped(_)) | Err(MapToError::ParentEntryHugePage) => (),
+            Err(error) => panic!("Failed to map device memory: {:?}", error),
+        }
+    }
+}
+
+fn unmap(start: u64, size: u64) {
+    let mut mapper = MAPPER.lock();
+    let mapper = mapper.as_mut().expect("Memory not initialized");
+    let mut frame_allocator = FRAME_ALLOCATOR.lock();
+    let frame_allocator = frame_allocator.as_mut().expect("Memory not initialized");
+
+    let start_page = Page::<Size4KiB>::containing_address(VirtAddr::new(start));
+    let end_page = Page::<Size4KiB>::containing_address(VirtAddr::new(start + size));
+    for page in Page::range(start_page, end_page) {
+        let (frame, flush) = mapper.unmap(page).expect("Failed to unmap program memory");
+        flush.flush();
+        frame_allocator.deallocate_frame(frame);
+    }
+}
+
+fn align_up(address: u64, align: u64) -> u64 {
+    (address + align - 1) & !(align - 1)
+}
+
 pub unsafe fn memcpy(dest: *mut u8, src: *const u8, n: usize) {
     let mut i = 0;
     while i < n {
@@ -0,0 +1,193 @@
+// PS/2 mouse on the auxiliary port of the keyboard controller, it interrupts
+// on IRQ12. Movement is added up in steps of a pixel of the 8x16 font, so the
+// position can be given in text cells. The cursor is an inverted cell.
+
+use core::sync::atomic::{AtomicBool, AtomicU8, AtomicUsize, Ordering};
+
+use spin::Mutex;
+use x86_64::instructions::port::Port;
+
+use crate::interrupts;
+use crate::terminal::WRITER;
+
+const DATA_PORT: u16 = 0x60;
+const STATUS_PORT: u16 = 0x64; // Commands for the controller go here too
+const STATUS_OUTPUT_FULL: u8 = 0x01;
+const STATUS_INPUT_FULL: u8 = 0x02;
+
+// Controller commands
+const ENABLE_AUX: u8 = 0xa8;
+const READ_CONFIG: u8 = 0x20;
+const WRITE_CONFIG: u8 = 0x60;
+const WRITE_AUX: u8 = 0xd4;
+const CONFIG_AUX_INTERRUPT: u8 = 0x02;
+const CONFIG_AUX_CLOCK_DISABLED: u8 = 0x20;
+
+// Mouse commands, the mouse acknowledges each one
+const SET_DEFAULTS: u8 = 0xf6;
+const ENABLE_REPORTING: u8 = 0xf4;
+const ACK: u8 = 0xfa;
+
+// How long to wait for the controller, in status reads
+const TIMEOUT: usize = 100_000;
+
+// The first byte of a packet has the buttons, the signs of the movement and
+// a bit that is always set
+const PACKET_ALWAYS_SET: u8 = 0x08;
+const PACKET_X_SIGN: u8 = 0x10;
+const PACKET_Y_SIGN: u8 = 0x20;
+const PACKET_OVERFLOW: u8 = 0xc0;
+const PACKET_BUTTONS: u8 = 0x07;
+
+// Movement steps per cell
+const STEPS_PER_COLUMN: i32 = 8;
+const STEPS_PER_ROW: i32 = 16;
+
+const MOUSE_IRQ: u8 = 12;
+const CASCADE_IRQ: u8 = 2;
+
+// Written by the interrupt handler, read by the Mouse syscall
+static PRESENT: AtomicBool = AtomicBool::new(false);
+static COLUMN: AtomicUsize = AtomicUsize::new(0);
+static ROW: AtomicUsize = AtomicUsize::new(0);
+static BUTTONS: AtomicU8 = AtomicU8::new(0);
+
+// Only used by the interrupt handler
+static MOUSE: Mutex<Mouse> = Mutex::new(Mouse {
+    packet: [0; 3],
+    received: 0,
+    x: 0,
+    y: 0,
+});
+
+struct Mouse {
+    packet: [u8; 3],
+    received: usize,  // Bytes of the packet so far
+    x: i32,           // In steps from the top left
+    y: i32,
+}
+
+// Turn on the auxiliary port and its interrupt, nothing happens without a
+// mouse. Call before interrupts are enabled.
+pub fn init() {
+    let present = command(ENABLE_AUX) &&
+        command(READ_CONFIG) &&
+        match read_data() {
+            Some(config) => command(WRITE_CONFIG) &&
+                write_data((config | CONFIG_AUX_INTERRUPT) & !CONFIG_AUX_CLOCK_DISABLED),
+            None => false,
+        } &&
+        send_to_mouse(SET_DEFAULTS) &&
+        send_to_mouse(ENABLE_REPORTING);
+    if present {
+        interrupts::unmask_irq(CASCADE_IRQ);
+        interrupts::unmask_irq(MOUSE_IRQ);
+    }
+    PRESENT.store(present, Ordering::Relaxed);
+}
+
+pub fn is_present() -> bool {
+    PRESENT.load(Ordering::Relaxed)
+}
+
+// Column, row and buttons (bit 0 left, 1 right, 2 middle), None without a mouse
+pub fn state() -> Option<(usize, usize, u8)> {
+    if !is_present() {
+        return None;
+    }
+    Some((COLUMN.load(Ordering::Relaxed), ROW.load(Ordering::Relaxed),
+          BUTTONS.load(Ordering::Relaxed)))
+}
+
+// Called from the interrupt handler for every byte the mouse sends
+pub fn handle_byte(byte: u8) {
+    let mut mouse = MOUSE.lock();
+    // Wait for a first byte to get back in sync after a lost byte
+    if mouse.received == 0 && byte & PACKET_ALWAYS_SET == 0 {
+        return;
+    }
+    let received = mouse.received;
+    mouse.packet[received] = byte;
+    mouse.received += 1;
+    if mouse.received < mouse.packet.len() {
+        return;
+    }
+    mouse.received = 0;
+
+    let (dx, dy, buttons) = match decode(mouse.packet) {
+        Some(movement) => movement,
+        None => return,
+    };
+    BUTTONS.store(buttons, Ordering::Relaxed);
+    // Up is positive for the mouse
+    mouse.x += dx;
+    mouse.y -= dy;
+
+    // Without the writer the position is clamped by the next packet
+    if let Some(mut writer) = WRITER.try_lock() {
+        mouse.x = mouse.x.clamp(0, writer.width() as i32 * STEPS_PER_COLUMN - 1);
+        mouse.y = mouse.y.clamp(0, writer.height() as i32 * STEPS_PER_ROW - 1);
+        let column = (mouse.x / STEPS_PER_COLUMN) as usize;
+        let row = (mouse.y / STEPS_PER_ROW) as usize;
+        COLUMN.store(column, Ordering::Relaxed);
+        ROW.store(row, Ordering::Relaxed);
+        writer.set_mouse_cursor(Some((row, column)));
+    }
+}
+
+// The movement and buttons of a packet, None if the movement overflowed
+fn decode(packet: [u8; 3]) -> Option<(i32, i32, u8)> {
+    let flags = packet[0];
+    if flags & PACKET_OVERFLOW != 0 {
+        return None;
+    }
+    // The movement is 9 bit two's complement, the sign is in the flags
+    let dx = packet[1] as i32 - if flags & PACKET_X_SIGN != 0 { 0x100 } else { 0 };
+    let dy = packet[2] as i32 - if flags & PACKET_Y_SIGN != 0 { 0x100 } else { 0 };
+    Some((dx, dy, flags & PACKET_BUTTONS))
+}
+
+fn wait_until(ready: impl Fn(u8) -> bool) -> bool {
+    let mut status_port: Port<u8> = Port::new(STATUS_PORT);
+    (0..TIMEOUT).any(|_| ready(unsafe { status_port.read() }))
+}
+
+fn command(command: u8) -> bool {
+    if !wait_until(|status| status & STATUS_INPUT_FULL == 0) {
+        return false;
+    }
+    unsafe { Port::new(STATUS_PORT).write(command) };
+    true
+}
+
+fn write_data(byte: u8) -> bool {
+    if !wait_until(|status| status & STATUS_INPUT_FULL == 0) {
+        return false;
+    }
+    unsafe { Port::new(DATA_PORT).write(byte) };
+    true
+}
+
+fn read_data() -> Option<u8> {
+    if !wait_until(|status| status & STATUS_OUTPUT_FULL != 0) {
+        return None;
+    }
+    Some(unsafe { Port::new(DATA_PORT).read() })
+}
+
+fn send_to_mouse(byte: u8) -> bool {
+    command(WRITE_AUX) && write_data(byte) && read_data() == Some(ACK)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test_case]
+    fn packets_are_decoded() {
+        assert_eq!(decode([0x08, 5, 3]), Some((5, 3, 0)));
+        assert_eq!(decode([0x08 | PACKET_X_SIGN | PACKET_Y_SIGN | 0x01, 0xff, 0xfe]),
+                   Some((-1, -2, 1)));
+        assert_eq!(decode([0x08 | 0x40, 0, 0]), None);
+    }
+}
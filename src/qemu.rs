@@ -0,0 +1,38 @@
+use x86_64::instructions::port::Port;
+
+// Port of the isa-debug-exit device, see the QEMU flags in tests/golden/run.sh
+// and tests/run-kernel-tests.sh
+const ISA_DEBUG_EXIT_PORT: u16 = 0xf4;
+
+// QEMU exits with (code << 1) | 1, so 33 for success and 35 for failure
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[repr(u32)]
+pub enum QemuExitCode {
+    Success = 0x10,
+    Failed = 0x11,
+}
+
+// ACPI shutdown ports of QEMU and of Bochs (and older QEMU versions), there
+// is no ACPI table parser to find the port of real hardware
+const QEMU_ACPI_PORT: u16 = 0x604;
+const BOCHS_ACPI_PORT: u16 = 0xb004;
+const ACPI_SLEEP: u16 = 0x2000;
+
+pub fn power_off() -> ! {
+    unsafe {
+        Port::new(QEMU_ACPI_PORT).write(ACPI_SLEEP);
+        Port::new(BOCHS_ACPI_PORT).write(ACPI_SLEEP);
+    }
+    // Still running, the machine has to be turned off by hand
+    crate::println!("It is now safe to turn off your computer");
+    crate::hlt_loop();
+}
+
+pub fn exit_qemu(exit_code: QemuExitCode) -> ! {
+    unsafe {
+        let mut port = Port::new(ISA_DEBUG_EXIT_PORT);
+        port.write(exit_code as u32);
+    }
+    // Only reached when not running in QEMU with isa-debug-exit
+    crate::hlt_loop();
+}
@@ -0,0 +1,147 @@
+// Optional header in front of an RCPU image, so a module can say where it is
+// loaded and started. Images without one are loaded at 0 and started at 0.
+//
+// The header is big-endian words:
+//   0-1    magic, "RCPU"
+//   2      format version, 1
+//   3      header size in words, the image follows it
+//   4      entry point
+//   5      load address
+//   6      image size in words
+//   7      stack size in words, 0 for all 64K
+//   8      BSS size in words, zeroed after the image
+//   9      flags, bit 0 set if the image is little-endian
+//   10     checksum, the wrapping sum of the image words
+//   11-26  title, ASCII padded with NULs
+
+use core::str;
+
+use super::RAM_WORDS;
+
+const MAGIC: [u16; 2] = [0x5243, 0x5055];
+const VERSION: u16 = 1;
+const HEADER_WORDS: usize = 27;
+const TITLE_BYTES: usize = 32;
+const FLAG_LITTLE_ENDIAN: u16 = 0x0001;
+
+// Why an image was rejected
+pub type Error = &'static str;
+
+#[derive(Debug, Clone, Copy)]
+pub struct Header {
+    pub header_size: usize,
+    pub entry: u16,
+    pub load_address: usize,
+    pub image_size: usize,
+    pub stack_size: usize,
+    pub bss_size: usize,
+    pub little_endian: bool,
+    title: [u8; TITLE_BYTES],
+}
+
+impl Header {
+    // Up to the first NUL, empty if it isn't ASCII
+    pub fn title(&self) -> &str {
+        let length = self.title.iter().position(|&byte| byte == 0).unwrap_or(TITLE_BYTES);
+        match str::from_utf8(&self.title[..length]) {
+            Ok(title) if title.is_ascii() => title,
+            _ => "",
+        }
+    }
+
+    // The image in the module
+    pub fn image<'a>(&self, module: &'a [u8]) -> &'a [u8] {
+        &module[self.header_size * 2..(self.header_size + self.image_size) * 2]
+    }
+
+    // The first word after the image and the BSS
+    pub fn end(&self) -> usize {
+        self.load_address + self.image_size + self.bss_size
+    }
+}
+
+// None for a legacy image without a header
+pub fn parse(module: &[u8]) -> Result<Option<Header>, Error> {
+    let word = |index: usize| u16::from_be_bytes([module[index * 2], module[index * 2 + 1]]);
+    if module.len() < 4 || [word(0), word(1)] != MAGIC {
+        return Ok(None);
+    }
+    if module.len() < HEADER_WORDS * 2 {
+        return Err("the header is cut off");
+    }
+    if word(2) != VERSION {
+        return Err("unsupported format version");
+    }
+
+    let flags = word(9);
+    let mut title = [0; TITLE_BYTES];
+    title.copy_from_slice(&module[11 * 2..11 * 2 + TITLE_BYTES]);
+    let header = Header {
+        header_size: word(3) as usize,
+        entry: word(4),
+        load_address: word(5) as usize,
+        image_size: word(6) as usize,
+        stack_size: if word(7) == 0 { RAM_WORDS } else { word(7) as usize },
+        bss_size: word(8) as usize,
+        little_endian: flags & FLAG_LITTLE_ENDIAN != 0,
+        title,
+    };
+    if header.header_size < HEADER_WORDS {
+        return Err("the header size is too small");
+    }
+    if (header.header_size + header.image_size) * 2 > module.len() {
+        return Err("the image is cut off");
+    }
+    if header.end() > RAM_WORDS {
+        return Err("the image does not fit in RCPU memory");
+    }
+
+    let checksum = header.image(module).chunks(2).fold(0u16, |sum, bytes| {
+        let bytes = [bytes[0], bytes[1]];
+        let value = if header.little_endian { u16::from_le_bytes(bytes) } else { u16::from_be_bytes(bytes) };
+        sum.wrapping_add(value)
+    });
+    if checksum != word(10) {
+        return Err("the checksum does not match");
+    }
+    Ok(Some(header))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use alloc::vec::Vec;
+
+    // A header for the image, which is big-endian words
+    fn module(image: &[u16], checksum: u16) -> Vec<u8> {
+        let mut words = [0u16; HEADER_WORDS];
+        words[..11].copy_from_slice(&[
+            MAGIC[0], MAGIC[1], VERSION, HEADER_WORDS as u16, 2, 0x100,
+            image.len() as u16, 0, 16, 0, checksum,
+        ]);
+        words[11] = u16::from_be_bytes(*b"Hi");
+        words.iter().chain(image).flat_map(|word| word.to_be_bytes().to_vec()).collect()
+    }
+
+    #[test_case]
+    fn legacy_images_have_no_header() {
+        assert!(parse(&[0x00, 0x4e, 0xff, 0xc1]).unwrap().is_none());
+    }
+
+    #[test_case]
+    fn header_is_parsed() {
+        let module = module(&[1, 2, 0xffff], 2);
+        let header = parse(&module).unwrap().unwrap();
+        assert_eq!(header.entry, 2);
+        assert_eq!(header.load_address, 0x100);
+        assert_eq!(header.end(), 0x100 + 3 + 16);
+        assert_eq!(header.stack_size, RAM_WORDS);
+        assert_eq!(header.title(), "Hi");
+        assert_eq!(header.image(&module), &[0, 1, 0, 2, 0xff, 0xff]);
+    }
+
+    #[test_case]
+    fn wrong_checksum_is_an_error() {
+        assert!(parse(&module(&[1, 2], 4)).is_err());
+    }
+}
@@ -1,22 +1,63 @@
+use alloc::collections::VecDeque;
+use alloc::string::{String, ToString};
 use core::convert::TryInto;
-use core::ops::DerefMut;
+use core::sync::atomic::Ordering;
 
 use crate::print;
 use crate::println;
+use crate::serial_println;
+use crate::cmdline;
 use crate::memory::memcpy;
 use crate::memory::swap_endianness;
+use crate::memory::ProgramWindow;
+use crate::memory::PROGRAM_AREA_SIZE;
+use crate::keyboard;
 use crate::keyboard::KEYBUFFER;
+use crate::terminal;
+use crate::graphics;
+use crate::mouse;
+use crate::status;
+use crate::tty;
+use crate::tty::{InputMode, LineEditor, LineEnd, CTRL_D};
 use crate::rcpu::operations::RCPUInstructionType;
 use crate::rcpu::operations::RCPUAthOperation;
 use crate::rcpu::operations::RCPUAthMode;
 use crate::rcpu::operations::RCPUOperation;
+use crate::rcpu::options::RCPUDivideByZero;
+use crate::rcpu::options::RCPUOptions;
 
 use multiboot2::ModuleTag;
-use multiboot2::MemoryArea;
 use pc_keyboard::DecodedKey;
 use pc_keyboard::KeyCode;
 
+mod header;
 mod operations;
+mod options;
+
+// How long a line Getc reads in cooked mode can get
+const GETC_LINE_SIZE: usize = 256;
+
+// What Getc returns of a cooked line, the end is returned like the key that
+// ended it. An interrupted line is thrown away
+fn cooked_characters(line: &[char], end: LineEnd) -> VecDeque<char> {
+    let mut chars: VecDeque<char> = line.iter().copied().collect();
+    match end {
+        LineEnd::Enter => chars.push_back('\n'),
+        LineEnd::Nul => chars.push_back('\0'),
+        LineEnd::Eof => chars.push_back(CTRL_D),
+        LineEnd::Interrupted => chars.clear(),
+        LineEnd::Full => (),
+    }
+    chars
+}
+
+// Where argv goes, None if the arguments would overlap the program
+fn arguments_start(arguments: &[&str], program_size: usize) -> Option<usize> {
+    let strings_size: usize = arguments.iter().map(|argument| argument.len() + 1).sum();
+    RAM_WORDS.checked_sub(arguments.len())?
+        .checked_sub(strings_size)
+        .filter(|&words| words >= program_size)
+}
 
 // TODO: split up mod.rs (runner) to state.rs
 #[derive(Debug,Clone,Copy)]
@@ -39,11 +80,46 @@ struct RCPUState {
     d: u16
 }
 
+// The module command line starts with the name of the program, options follow
+pub fn program_name(command_line: &str) -> &str {
+    command_line.split_whitespace().next().unwrap_or("")
+}
+
+// The title in the header of a program, if it has one
+pub fn program_title(tag: &ModuleTag) -> Option<String> {
+    match header::parse(module_bytes(tag)) {
+        Ok(Some(header)) if !header.title().is_empty() => Some(header.title().to_string()),
+        _ => None,
+    }
+}
+
+// The contents of a module, they stay in memory because the frame allocator
+// skips the modules
+fn module_bytes(tag: &ModuleTag) -> &'static [u8] {
+    let size = (tag.end_address() - tag.start_address()) as usize;
+    unsafe { core::slice::from_raw_parts(tag.start_address() as *const u8, size) }
+}
+
+// <program>.in modules are the scripted stdin of <program>
+pub fn is_input_module(command_line: &str) -> bool {
+    program_name(command_line).ends_with(".in")
+}
+
 #[derive(Debug,Clone,Copy)]
 pub enum RCPUSyscall {
     Printf = 0,
     Fgets,
-    Getc
+    Getc,
+    SetOutputMode,
+    GraphicsMode,
+    PlotPixel,
+    FillRect,
+    Blit,
+    SetPalette,
+    Ioctl,
+    Signal,
+    Mouse,
+    Args
 }
 
 impl From<u16> for RCPUSyscall {
@@ -52,18 +128,59 @@ impl From<u16> for RCPUSyscall {
             0 => RCPUSyscall::Printf,
             1 => RCPUSyscall::Fgets,
             2 => RCPUSyscall::Getc,
+            3 => RCPUSyscall::SetOutputMode,
+            4 => RCPUSyscall::GraphicsMode,
+            5 => RCPUSyscall::PlotPixel,
+            6 => RCPUSyscall::FillRect,
+            7 => RCPUSyscall::Blit,
+            8 => RCPUSyscall::SetPalette,
+            9 => RCPUSyscall::Ioctl,
+            10 => RCPUSyscall::Signal,
+            11 => RCPUSyscall::Mouse,
+            12 => RCPUSyscall::Args,
             _ => panic!("Invalid syscall number {}", value)
         }
     }
 }
 
-#[derive(Debug,Clone,Copy)]
+// The RAM of a program in words
+const RAM_WORDS: usize = PROGRAM_AREA_SIZE / 2;
+
+// Ioctl requests
+const IOCTL_GET_INPUT_MODE: u16 = 0;
+const IOCTL_SET_INPUT_MODE: u16 = 1;
+
+// Signals a program can handle, Ctrl+C is the only one
+const SIGNAL_INTERRUPT: u16 = 0;
+
+// How the bytes a program prints are shown
+#[derive(Debug,Clone,Copy,PartialEq,Eq)]
+pub enum RCPUOutputMode {
+    // Bytes are Latin-1 characters, translated to code page 437
+    Translated = 0,
+    // Bytes are code page 437 glyphs, for text mode art
+    Raw
+}
+
+#[derive(Debug)]
 pub struct RCPUProgram {
     pub running: bool,
     ram_start: *mut u16,
     stack_start: *mut u16,
     stack_end: *mut u16,
     state: RCPUState,
+    options: RCPUOptions,
+    output_mode: RCPUOutputMode,
+    input_mode: InputMode,
+    line_editor: LineEditor,
+    cooked_line: VecDeque<char>, // What Getc didn't return yet of the last cooked line
+    dropped_keys: usize,       // By the queues of the input modes used before
+    interrupt_handler: Option<u16>, // Called on Ctrl+C, None stops the program
+    script: &'static [u8],     // What is left of the scripted stdin
+    instructions: u64,         // Executed so far, for the budget
+    arguments: (u16, u16),     // argc and the address of argv
+    #[allow(dead_code)] // Keeps the memory mapped for as long as the program lives
+    window: ProgramWindow,
 }
 
 impl RCPUProgram {
@@ -90,7 +207,7 @@ impl RCPUProgram {
             let mem_sp = self.stack_start.offset(
                 self.get_register(RCPURegister::SP).try_into().unwrap()
             );
-            if mem_sp > self.stack_end {
+            if mem_sp >= self.stack_end {
                 panic!("Stack overflow");
             }
             *mem_sp = swap_endianness(value)
@@ -145,6 +262,12 @@ impl RCPUProgram {
         self.set_register(register, value.wrapping_sub(1));
     }
 
+    // Stop the program because it did something undefined
+    fn fault(&mut self, message: &str) {
+        println!("\nRCPU fault at IP {}: {}", self.state.ip, message);
+        self.running = false;
+    }
+
     fn syscall(&mut self) {
         let syscall = RCPUSyscall::from(self.pop());
         match syscall {
@@ -165,6 +288,162 @@ impl RCPUProgram {
                 let chars_read = self.get_string(str_ptr, size, stream_num);
                 self.push(chars_read);
             }
+            RCPUSyscall::SetOutputMode => {
+                let mode = self.pop();
+                let stream_num = self.pop();
+                self.set_output_mode(mode, stream_num);
+            }
+            RCPUSyscall::GraphicsMode => {
+                let mode = self.pop();
+                let success = self.set_graphics_mode(mode);
+                self.push(success as u16);
+            }
+            RCPUSyscall::PlotPixel => {
+                let x = self.pop();
+                let y = self.pop();
+                let color = self.pop();
+                graphics::plot(x as usize, y as usize, color as u8);
+            }
+            RCPUSyscall::FillRect => {
+                let x = self.pop();
+                let y = self.pop();
+                let width = self.pop();
+                let height = self.pop();
+                let color = self.pop();
+                graphics::fill_rect(x as usize, y as usize, width as usize, height as usize, color as u8);
+            }
+            RCPUSyscall::Blit => {
+                let image_ptr = self.pop();
+                let x = self.pop();
+                let y = self.pop();
+                let width = self.pop();
+                let height = self.pop();
+                self.blit(image_ptr, x, y, width, height);
+            }
+            RCPUSyscall::SetPalette => {
+                let index = self.pop();
+                let red = self.pop();
+                let green = self.pop();
+                let blue = self.pop();
+                graphics::set_palette(index as u8, (red as u8, green as u8, blue as u8));
+            }
+            RCPUSyscall::Ioctl => {
+                let stream_num = self.pop();
+                let request = self.pop();
+                let argument = self.pop();
+                let result = self.ioctl(stream_num, request, argument);
+                self.push(result);
+            }
+            RCPUSyscall::Signal => {
+                let signal = self.pop();
+                let handler = self.pop();
+                self.set_signal_handler(signal, handler);
+            }
+            // Without a mouse it stays in the top left with no buttons
+            RCPUSyscall::Mouse => {
+                let (column, row, buttons) = mouse::state().unwrap_or((0, 0, 0));
+                self.push(column as u16);
+                self.push(row as u16);
+                self.push(buttons as u16);
+            }
+            RCPUSyscall::Args => {
+                let (argc, argv) = self.arguments;
+                self.push(argv);
+                self.push(argc);
+            }
+        }
+    }
+
+    // Handler 0 goes back to stopping the program
+    fn set_signal_handler(&mut self, signal: u16, handler: u16) {
+        if signal != SIGNAL_INTERRUPT {
+            panic!("Invalid signal {}", signal);
+        }
+        self.interrupt_handler = if handler == 0 { None } else { Some(handler) };
+    }
+
+    // Ctrl+C was pressed. The handler is called like CAL would, so RET
+    // continues with the instruction that was about to run.
+    fn interrupt(&mut self) {
+        match self.interrupt_handler {
+            Some(handler) => {
+                self.push(self.state.ip);
+                self.state.ip = handler;
+            }
+            None => {
+                println!("^C");
+                self.running = false;
+            }
+        }
+    }
+
+    // Get or set the input mode of stdin, setting returns the previous one
+    fn ioctl(&mut self, stream_num: u16, request: u16, argument: u16) -> u16 {
+        if stream_num != 0 {
+            panic!("Invalid stream number: {}", stream_num);
+        }
+        match request {
+            IOCTL_GET_INPUT_MODE => self.input_mode as u16,
+            IOCTL_SET_INPUT_MODE => {
+                let previous = self.input_mode;
+                self.set_input_mode(InputMode::from(argument));
+                previous as u16
+            }
+            _ => panic!("Invalid ioctl request {}", request)
+        }
+    }
+
+    fn set_input_mode(&mut self, mode: InputMode) {
+        self.dropped_keys += keyboard::take_dropped(self.input_mode);
+        // The keyboard fills both buffers, only the one of the new mode has
+        // keys the program hasn't seen yet
+        if mode == InputMode::Raw && self.input_mode != InputMode::Raw {
+            keyboard::SCANCODES.clear();
+        } else if mode != InputMode::Raw && self.input_mode == InputMode::Raw {
+            KEYBUFFER.clear();
+        }
+        if mode != InputMode::Cooked {
+            self.cooked_line.clear();
+        }
+        self.input_mode = mode;
+    }
+
+    // 0 is the terminal, 1 the 320x200 canvas. Returns whether it worked,
+    // graphics need a framebuffer.
+    fn set_graphics_mode(&mut self, mode: u16) -> bool {
+        match mode {
+            0 => {
+                graphics::leave();
+                true
+            }
+            1 => graphics::enter(),
+            _ => panic!("Invalid graphics mode {}", mode)
+        }
+    }
+
+    // Pixels are stored one per word, like the characters of a string
+    fn blit(&mut self, image_ptr: u16, x: u16, y: u16, width: u16, height: u16) {
+        graphics::blit(x as usize, y as usize, width as usize, height as usize, |column, row| {
+            let offset = row * width as usize + column;
+            self.read(image_ptr.wrapping_add(offset as u16)) as u8
+        });
+    }
+
+    fn set_output_mode(&mut self, mode: u16, stream_num: u16) {
+        if stream_num != 1 {
+            panic!("Invalid stream number: {}", stream_num);
+        }
+        self.output_mode = match mode {
+            0 => RCPUOutputMode::Translated,
+            1 => RCPUOutputMode::Raw,
+            _ => panic!("Invalid output mode {}", mode)
+        };
+    }
+
+    fn print_char(&self, c: char) {
+        match self.output_mode {
+            RCPUOutputMode::Translated => print!("{}", c),
+            RCPUOutputMode::Raw => terminal::print_raw_byte(c as u8),
         }
     }
 
@@ -176,7 +455,7 @@ impl RCPUProgram {
             if should_format && formatting {
                 match curr_char {
                     'd' => print!("{}", self.pop()),
-                    'c' => print!("{}", self.pop() as u8 as char),
+                    'c' => { let value = self.pop(); self.print_char(value as u8 as char); },
                     // This needs to be split up due to compiler problems
                     's' => { let value = self.pop(); self.print_string(value, false); },
                     '%' => print!("%"),
@@ -186,7 +465,7 @@ impl RCPUProgram {
             } else if should_format && curr_char == '%' {
                 formatting = true;
             } else {
-                print!("{}", curr_char);
+                self.print_char(curr_char);
             }
             curr_char_idx += 1;
             curr_char = self.read(curr_char_idx) as u8 as char;
@@ -199,17 +478,39 @@ impl RCPUProgram {
             panic!("Invalid stream number: {}", stream_num);
         }
 
-        let mut ret: u16 = u16::MAX;
-        x86_64::instructions::interrupts::without_interrupts(|| {
-            let mut keybuffer = KEYBUFFER.lock();
-            for key in keybuffer.deref_mut() {
-                if let DecodedKey::Unicode(c) = key {
-                    ret = c as u16;
-                    break;
-                }
+        if self.input_mode == InputMode::Raw {
+            return keyboard::SCANCODES.pop().map_or(u16::MAX, |scancode| scancode as u16);
+        }
+        if self.input_mode == InputMode::Cooked {
+            return self.get_cooked_character();
+        }
+
+        // Keys without a character are skipped
+        while let Some(key) = self.script_key().or_else(|| KEYBUFFER.pop()) {
+            if let DecodedKey::Unicode(c) = key {
+                return c as u16;
             }
-        });
-        return ret as u16;
+        }
+        u16::MAX
+    }
+
+    // Characters come from a line edited like in Fgets, the next line is read
+    // once all of the last one was returned
+    fn get_cooked_character(&mut self) -> u16 {
+        if self.cooked_line.is_empty() {
+            self.line_editor.start(GETC_LINE_SIZE);
+            let end = loop {
+                let key = match self.wait_for_key() {
+                    Some(key) => key,
+                    None => break LineEnd::Interrupted,
+                };
+                if let Some(end) = self.line_editor.handle_key(key) {
+                    break end;
+                }
+            };
+            self.cooked_line = cooked_characters(self.line_editor.line(), end);
+        }
+        self.cooked_line.pop_front().map_or(u16::MAX, |c| c as u16)
     }
 
     fn get_string(&mut self, str_ptr: u16, size: u16, stream_num: u16) -> u16 {
@@ -217,39 +518,100 @@ impl RCPUProgram {
         if stream_num != 0 {
             panic!("Invalid stream number: {}", stream_num);
         }
+        if size == 0 {
+            self.write(str_ptr, 0);
+            return 0;
+        }
 
-        let mut chars_read = 0;
-        let mut hit_nullbyte = false;
-        while chars_read < size && !hit_nullbyte {
-            let mut char_read = false;
-            x86_64::instructions::interrupts::without_interrupts(|| {
-                let mut keybuffer = KEYBUFFER.lock();
-                if let Some(key) = keybuffer.deref_mut().next() {
-                    let ret = match key {
-                        DecodedKey::Unicode(c) => Some(c),
-                        DecodedKey::RawKey(KeyCode::F1) => Some('\0'),
-                        _ => None
-                    };
-                    if let Some(c) = ret {
-                        self.write(str_ptr + chars_read, c as u16);
-                        char_read = true;
-                        hit_nullbyte = c == '\0';
-                    }
-                }
-            });
+        let (chars_read, hit_nullbyte) = match self.input_mode {
+            InputMode::Cooked => self.read_line(str_ptr, size),
+            InputMode::Cbreak => self.read_keys(str_ptr, size),
+            InputMode::Raw => self.read_scancodes(str_ptr, size),
+        };
+
+        // Add ending nullbyte
+        if !hit_nullbyte {
+            self.write(str_ptr + chars_read, 0);
+        }
+        chars_read
+    }
 
-            if char_read {
+    // Let the user edit the line until it is submitted
+    fn read_line(&mut self, str_ptr: u16, size: u16) -> (u16, bool) {
+        self.line_editor.start(size as usize);
+        let end = loop {
+            let key = match self.wait_for_key() {
+                Some(key) => key,
+                None => break LineEnd::Interrupted,
+            };
+            if let Some(end) = self.line_editor.handle_key(key) {
+                break end;
+            }
+        };
+        if end == LineEnd::Interrupted {
+            return (0, false);
+        }
+
+        let mut chars_read = 0;
+        for &c in self.line_editor.line() {
+            self.write(str_ptr + chars_read, c as u16);
+            chars_read += 1;
+        }
+        match end {
+            LineEnd::Enter if self.options.store_newline && chars_read < size => {
+                self.write(str_ptr + chars_read, '\n' as u16);
                 chars_read += 1;
-            } else {
-                x86_64::instructions::interrupts::enable_and_hlt();
             }
+            // The nullbyte is part of the string
+            LineEnd::Nul => {
+                self.write(str_ptr + chars_read, 0);
+                return (chars_read + 1, true);
+            }
+            _ => (),
         }
+        (chars_read, false)
+    }
 
-        // Add ending nullbyte
-        if !hit_nullbyte {
-            self.write(str_ptr + chars_read, 0);
+    // Store the characters as they are typed, F1 enters a nullbyte and
+    // Ctrl+D ends the input early
+    fn read_keys(&mut self, str_ptr: u16, size: u16) -> (u16, bool) {
+        let mut chars_read = 0;
+        while chars_read < size {
+            let c = match self.wait_for_key() {
+                Some(DecodedKey::Unicode(CTRL_D)) | None => break,
+                Some(DecodedKey::Unicode(c)) => c,
+                Some(DecodedKey::RawKey(KeyCode::F1)) => '\0',
+                _ => continue,
+            };
+            self.write(str_ptr + chars_read, c as u16);
+            chars_read += 1;
+            if c == '\0' {
+                return (chars_read, true);
+            }
+        }
+        (chars_read, false)
+    }
+
+    // Scripted stdin comes first, then the keyboard
+    fn wait_for_key(&mut self) -> Option<DecodedKey> {
+        self.script_key().or_else(keyboard::wait_for_key)
+    }
+
+    // The next byte of the scripted stdin, typed like a key
+    fn script_key(&mut self) -> Option<DecodedKey> {
+        let (&byte, rest) = self.script.split_first()?;
+        self.script = rest;
+        Some(DecodedKey::Unicode(byte as char))
+    }
+
+    fn read_scancodes(&mut self, str_ptr: u16, size: u16) -> (u16, bool) {
+        for offset in 0..size {
+            match keyboard::wait_for_scancode() {
+                Some(scancode) => self.write(str_ptr + offset, scancode as u16),
+                None => return (offset, false),
+            }
         }
-        return chars_read;
+        (size, false)
     }
 
     fn execute(&mut self, operation: RCPUOperation) {
@@ -304,7 +666,17 @@ impl RCPUProgram {
                         new_value = dest_value.wrapping_mul(src_value);
                     }
                     RCPUAthOperation::Divide => {
-                        new_value = dest_value.wrapping_div(src_value);
+                        if src_value == 0 {
+                            match self.options.divide_by_zero {
+                                RCPUDivideByZero::Fault => {
+                                    self.fault("Division by zero");
+                                    return;
+                                }
+                                RCPUDivideByZero::Value(value) => new_value = value,
+                            }
+                        } else {
+                            new_value = dest_value.wrapping_div(src_value);
+                        }
                     }
                     RCPUAthOperation::LeftShift => {
                         new_value = src_value.wrapping_shl(
@@ -401,6 +773,12 @@ impl RCPUProgram {
     }
 
     pub fn step(&mut self) {
+        tty::suspend_if_requested();
+        if tty::take_interrupt() {
+            self.interrupt();
+            return;
+        }
+
         // Get the current opcode
         let binary_opcode: u16 = self.read(self.state.ip.into());
         
@@ -408,34 +786,110 @@ impl RCPUProgram {
         let operation = RCPUOperation {
             opcode: binary_opcode
         };
+        let config = cmdline::config();
+        if config.budget == Some(self.instructions) {
+            println!("\nStopped after the budget of {} instructions", self.instructions);
+            self.running = false;
+            return;
+        }
+        if config.trace {
+            serial_println!("{:#06x} {:?} {:?}", self.state.ip, operation.instruction_type(), self.state);
+        }
         self.execute(operation);
+        self.instructions += 1;
+        status::INSTRUCTIONS.fetch_add(1, Ordering::Relaxed);
 
         // println!("New state: {:?}", self.state);
     }
 
-    pub fn from_module_tag(tag: &ModuleTag, rcpu_mem_start: usize, rcpu_mem_end: usize) -> RCPUProgram {
+    // Feed the bytes of a module to stdin before the keyboard
+    pub fn set_stdin(&mut self, tag: &ModuleTag) {
+        self.script = module_bytes(tag);
+    }
+
+    // How many keys the program missed because the queue it reads was full
+    pub fn take_dropped_keys(&mut self) -> usize {
+        let dropped = self.dropped_keys + keyboard::take_dropped(self.input_mode);
+        self.dropped_keys = 0;
+        dropped
+    }
+
+    // Put the arguments at the end of the RAM: argv, a pointer to each
+    // argument, followed by the arguments as NUL-terminated strings
+    fn set_arguments(&mut self, arguments: &[&str], program_size: usize) -> Result<(), header::Error> {
+        let argc = arguments.len();
+        let argv = arguments_start(arguments, program_size)
+            .ok_or("the arguments do not fit in RCPU memory")? as u16;
+        let mut string = argv as usize + argc;
+        for (index, argument) in arguments.iter().enumerate() {
+            self.write(argv + index as u16, string as u16);
+            for (offset, byte) in argument.bytes().enumerate() {
+                self.write((string + offset) as u16, byte as u16);
+            }
+            self.write((string + argument.len()) as u16, 0);
+            string += argument.len() + 1;
+        }
+        self.arguments = (argc as u16, argv);
+        Ok(())
+    }
+
+    // An image that can't be loaded is rejected before any memory is mapped
+    pub fn from_module_tag(tag: &ModuleTag) -> Result<RCPUProgram, header::Error> {
         // Print the name
-        println!("Booting {}", tag.name());
+        let name = program_name(tag.name());
+        println!("Booting {}", name);
+
+        // A legacy image is loaded at 0 and started at 0
+        let module = module_bytes(tag);
+        let header = header::parse(module)?;
+        let image = header.map_or(module, |header| header.image(module));
+        let load_address = header.map_or(0, |header| header.load_address);
+        let stack_size = header.map_or(RAM_WORDS, |header| header.stack_size);
+        let program_end = header.map_or((image.len() + 1) / 2, |header| header.end());
+        if image.len() > PROGRAM_AREA_SIZE {
+            return Err("the image does not fit in RCPU memory");
+        }
 
-        // Set the start and end pointers
-        let ram_start = rcpu_mem_start as *mut u16;
-        let stack_start = (rcpu_mem_start + 65536) as *mut u16;
-        let stack_end = rcpu_mem_end as *mut u16;
+        // Map fresh memory for the program, the RAM is followed by the stack.
+        // Both start out zeroed, which clears the BSS.
+        let window = ProgramWindow::allocate();
+        let ram_start = window.ram;
+        let stack_start = window.stack;
+        let stack_end = unsafe { stack_start.add(stack_size) };
 
-        // Copy the program to the RAM
+        // Copy the program to the RAM, the words are read as big-endian
         unsafe {
+            let load_start = ram_start.add(load_address);
             memcpy(
-                ram_start as *mut u8,
-                tag.start_address() as *const u8,
-                (tag.end_address() - tag.start_address()) as usize
+                load_start as *mut u8,
+                image.as_ptr(),
+                image.len()
             );
+            if header.map_or(false, |header| header.little_endian) {
+                for index in 0..image.len() / 2 {
+                    let word = load_start.add(index);
+                    *word = swap_endianness(*word);
+                }
+            }
         }
 
-        RCPUProgram {
+        let (options, arguments) = RCPUOptions::from_command_line(tag.name());
+        let mut program = RCPUProgram {
             running: true,
             ram_start: ram_start,
             stack_start: stack_start,
             stack_end: stack_end,
+            options: options,
+            output_mode: RCPUOutputMode::Translated,
+            input_mode: InputMode::Cooked,
+            line_editor: LineEditor::new(),
+            cooked_line: VecDeque::new(),
+            dropped_keys: 0,
+            interrupt_handler: None,
+            script: &[],
+            instructions: 0,
+            arguments: (0, 0),
+            window: window,
             state: RCPUState {
                 ip: 0,
                 sp: 0,
@@ -444,7 +898,42 @@ impl RCPUProgram {
                 c: 0,
                 d: 0
             }
+        };
+        if let Some(header) = header {
+            program.state.ip = header.entry;
         }
+        program.set_arguments(&arguments, program_end)?;
+        Ok(program)
     }
 
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use alloc::vec::Vec;
+
+    #[test_case]
+    fn arguments_go_at_the_end_of_the_ram() {
+        assert_eq!(arguments_start(&["prog", "-v"], 16), Some(RAM_WORDS - 2 - 8));
+        assert_eq!(arguments_start(&["prog"], RAM_WORDS - 6), Some(RAM_WORDS - 6));
+    }
+
+    #[test_case]
+    fn oversized_arguments_do_not_fit() {
+        let long = "x".repeat(RAM_WORDS);
+        assert_eq!(arguments_start(&["prog", &long], 0), None);
+        assert_eq!(arguments_start(&["prog"], RAM_WORDS - 5), None);
+    }
+
+    #[test_case]
+    fn cooked_line_ends_like_the_key() {
+        let line = ['h', 'i'];
+        let chars = |end| cooked_characters(&line, end).into_iter().collect::<Vec<char>>();
+        assert_eq!(chars(LineEnd::Enter), ['h', 'i', '\n']);
+        assert_eq!(chars(LineEnd::Nul), ['h', 'i', '\0']);
+        assert_eq!(chars(LineEnd::Eof), ['h', 'i', CTRL_D]);
+        assert_eq!(chars(LineEnd::Full), ['h', 'i']);
+        assert!(chars(LineEnd::Interrupted).is_empty());
+    }
+}
@@ -0,0 +1,63 @@
+use alloc::vec::Vec;
+
+use crate::println;
+
+// What happens when a program divides by 0
+#[derive(Debug,Clone,Copy)]
+pub enum RCPUDivideByZero {
+    // Stop the program with an RCPU fault
+    Fault,
+    // Store this value as the result (the reference emulator uses 0xFFFF)
+    Value(u16),
+}
+
+// Per-program settings, passed as `key=value` words after the module name.
+// The other words, and every word after `--`, are the arguments of the program.
+#[derive(Debug,Clone,Copy)]
+pub struct RCPUOptions {
+    pub divide_by_zero: RCPUDivideByZero,
+    // Whether Fgets stores the \n of Enter
+    pub store_newline: bool,
+}
+
+impl RCPUOptions {
+    // The options and the arguments, starting with the name of the program
+    pub fn from_command_line(command_line: &str) -> (RCPUOptions, Vec<&str>) {
+        let mut options = RCPUOptions {
+            divide_by_zero: RCPUDivideByZero::Fault,
+            store_newline: true,
+        };
+
+        // The first word is the name of the program
+        let mut words = command_line.split_whitespace();
+        let mut arguments: Vec<&str> = words.next().into_iter().collect();
+        while let Some(word) = words.next() {
+            match word.split_once('=') {
+                Some(("divzero", "fault")) => {
+                    options.divide_by_zero = RCPUDivideByZero::Fault;
+                }
+                Some(("divzero", value)) => match parse_number(value) {
+                    Some(value) => options.divide_by_zero = RCPUDivideByZero::Value(value),
+                    None => println!("Invalid divzero value {}", value),
+                },
+                Some(("newline", "keep")) => options.store_newline = true,
+                Some(("newline", "drop")) => options.store_newline = false,
+                _ if word == "--" => {
+                    arguments.extend(words.by_ref());
+                }
+                _ => arguments.push(word),
+            }
+        }
+
+        (options, arguments)
+    }
+}
+
+// Parse a decimal or 0x-prefixed hexadecimal number
+fn parse_number(value: &str) -> Option<u16> {
+    if let Some(hex) = value.strip_prefix("0x") {
+        u16::from_str_radix(hex, 16).ok()
+    } else {
+        value.parse().ok()
+    }
+}
@@ -0,0 +1,60 @@
+use core::fmt;
+use core::sync::atomic::{AtomicBool, Ordering};
+
+use lazy_static::lazy_static;
+use spin::Mutex;
+use uart_16550::SerialPort;
+
+use crate::interrupts;
+
+const COM1: u16 = 0x3F8;
+const COM1_IRQ: u8 = 4;
+
+// "Static" SerialPort instance for COM1, also enables its receive interrupt
+lazy_static! {
+    pub static ref SERIAL1: Mutex<SerialPort> = {
+        let mut serial_port = unsafe { SerialPort::new(COM1) };
+        serial_port.init();
+        Mutex::new(serial_port)
+    };
+}
+
+// Whether everything written to the console is also written to COM1
+static MIRROR: AtomicBool = AtomicBool::new(true);
+
+// The PICs keep the masks of the firmware when they are initialized, which
+// masks COM1
+pub fn init() {
+    lazy_static::initialize(&SERIAL1);
+    interrupts::unmask_irq(COM1_IRQ);
+}
+
+pub fn set_mirror(enabled: bool) {
+    MIRROR.store(enabled, Ordering::Relaxed);
+}
+
+pub fn is_mirrored() -> bool {
+    MIRROR.load(Ordering::Relaxed)
+}
+
+// serial_print!() macro
+#[macro_export]
+macro_rules! serial_print {
+    ($($arg:tt)*) => ($crate::serial::_print(format_args!($($arg)*)));
+}
+
+#[macro_export]
+macro_rules! serial_println {
+    () => ($crate::serial_print!("\n"));
+    ($($arg:tt)*) => ($crate::serial_print!("{}\n", format_args!($($arg)*)));
+}
+
+#[doc(hidden)]
+pub fn _print(args: fmt::Arguments) {
+    use core::fmt::Write;
+    use x86_64::instructions::interrupts;
+
+    interrupts::without_interrupts(|| {
+        SERIAL1.lock().write_fmt(args).unwrap();
+    });
+}
@@ -0,0 +1,135 @@
+// The status bar on the top row: the running program and whether it waits for
+// input, how fast it runs, the uptime, the keyboard layout and the free heap.
+// It is drawn from the timer interrupt, so nothing here allocates or waits for
+// a lock.
+
+use core::fmt;
+use core::fmt::Write;
+use core::sync::atomic::{AtomicBool, AtomicU64, Ordering};
+
+use spin::Mutex;
+
+use crate::allocator;
+use crate::keyboard;
+use crate::terminal::WRITER;
+use crate::timer::TICKS_PER_SECOND;
+
+const MAX_NAME_LEN: usize = 32;
+const MAX_STATUS_LEN: usize = 160;
+// Redraw ten times a second
+const REFRESH_TICKS: u64 = TICKS_PER_SECOND / 10;
+
+static ENABLED: AtomicBool = AtomicBool::new(false);
+static WAITING_FOR_INPUT: AtomicBool = AtomicBool::new(false);
+static SUSPENDED: AtomicBool = AtomicBool::new(false);
+// RCPU instructions executed since boot, and how many in the last second
+pub static INSTRUCTIONS: AtomicU64 = AtomicU64::new(0);
+static LAST_INSTRUCTIONS: AtomicU64 = AtomicU64::new(0);
+static INSTRUCTIONS_PER_SECOND: AtomicU64 = AtomicU64::new(0);
+
+static PROGRAM_NAME: Mutex<FixedString<MAX_NAME_LEN>> = Mutex::new(FixedString::new());
+
+// A string on the stack, writes that don't fit are cut off
+struct FixedString<const N: usize> {
+    bytes: [u8; N],
+    len: usize,
+}
+
+impl<const N: usize> FixedString<N> {
+    const fn new() -> FixedString<N> {
+        FixedString { bytes: [0; N], len: 0 }
+    }
+
+    fn as_str(&self) -> &str {
+        core::str::from_utf8(&self.bytes[..self.len]).unwrap_or("")
+    }
+
+    fn clear(&mut self) {
+        self.len = 0;
+    }
+}
+
+impl<const N: usize> fmt::Write for FixedString<N> {
+    fn write_str(&mut self, s: &str) -> fmt::Result {
+        for c in s.chars() {
+            if self.len + c.len_utf8() > N {
+                break;
+            }
+            c.encode_utf8(&mut self.bytes[self.len..]);
+            self.len += c.len_utf8();
+        }
+        Ok(())
+    }
+}
+
+// Reserve the top row and start drawing the status bar
+pub fn enable() {
+    x86_64::instructions::interrupts::without_interrupts(|| {
+        WRITER.lock().reserve_status_row();
+    });
+    ENABLED.store(true, Ordering::Relaxed);
+}
+
+// The program that is running, None when back in the kernel
+pub fn set_program(name: Option<&str>) {
+    x86_64::instructions::interrupts::without_interrupts(|| {
+        let mut program_name = PROGRAM_NAME.lock();
+        program_name.clear();
+        if let Some(name) = name {
+            let _ = program_name.write_str(name);
+        }
+    });
+}
+
+pub fn set_waiting_for_input(waiting: bool) {
+    WAITING_FOR_INPUT.store(waiting, Ordering::Relaxed);
+}
+
+pub fn set_suspended(suspended: bool) {
+    SUSPENDED.store(suspended, Ordering::Relaxed);
+}
+
+// Called from the timer interrupt
+pub fn tick(ticks: u64) {
+    if ticks % TICKS_PER_SECOND == 0 {
+        let instructions = INSTRUCTIONS.load(Ordering::Relaxed);
+        let last = LAST_INSTRUCTIONS.swap(instructions, Ordering::Relaxed);
+        INSTRUCTIONS_PER_SECOND.store(instructions - last, Ordering::Relaxed);
+    }
+    if ticks % REFRESH_TICKS == 0 && ENABLED.load(Ordering::Relaxed) {
+        refresh(ticks);
+    }
+}
+
+fn refresh(ticks: u64) {
+    let mut status = FixedString::<MAX_STATUS_LEN>::new();
+    match PROGRAM_NAME.try_lock() {
+        Some(name) if name.len > 0 => {
+            let state = if SUSPENDED.load(Ordering::Relaxed) {
+                "suspended, Ctrl+Z continues"
+            } else if WAITING_FOR_INPUT.load(Ordering::Relaxed) {
+                "waiting for input"
+            } else {
+                "running"
+            };
+            let _ = write!(status, " {} ({})", name.as_str(), state);
+        }
+        _ => {
+            let _ = write!(status, " RCPU_OS");
+        }
+    }
+
+    let seconds = ticks / TICKS_PER_SECOND;
+    let _ = write!(status, " | {} ips | up {}:{:02}:{:02} | {}",
+        INSTRUCTIONS_PER_SECOND.load(Ordering::Relaxed),
+        seconds / 3600, seconds / 60 % 60, seconds % 60,
+        keyboard::layout().name());
+    if let Some(free) = allocator::free() {
+        let _ = write!(status, " | {} KiB free", free / 1024);
+    }
+
+    // Skip this refresh if someone is printing, the next one will do
+    if let Some(mut writer) = WRITER.try_lock() {
+        writer.set_status(status.as_str());
+    }
+}
@@ -0,0 +1,153 @@
+// Parser for the subset of ANSI/VT100 escape sequences the terminal supports.
+// It is fed one byte at a time and tells the writer what to do with it.
+
+const ESCAPE: u8 = 0x1b;
+const MAX_PARAMS: usize = 8;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum State {
+    Ground,     // Normal output
+    Escape,     // After ESC
+    Csi,        // After ESC [
+}
+
+// A complete control sequence: ESC [ params final_byte
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Csi {
+    params: [u16; MAX_PARAMS],
+    num_params: usize,
+    pub final_byte: u8,
+}
+
+impl Csi {
+    // Missing and 0 parameters mean the default for most sequences
+    pub fn param_or(&self, index: usize, default: u16) -> u16 {
+        if index < self.num_params && self.params[index] != 0 {
+            self.params[index]
+        } else {
+            default
+        }
+    }
+
+    pub fn params(&self) -> &[u16] {
+        &self.params[..self.num_params]
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Action {
+    Print(u8),        // Not part of a sequence, handle it as usual
+    Csi(Csi),         // A complete control sequence
+    SaveCursor,       // ESC 7
+    RestoreCursor,    // ESC 8
+    Nothing,          // Part of a sequence that isn't complete yet
+}
+
+#[derive(Debug, Clone, Copy)]
+pub struct Parser {
+    state: State,
+    params: [u16; MAX_PARAMS],
+    num_params: usize,
+}
+
+impl Parser {
+    pub const fn new() -> Parser {
+        Parser {
+            state: State::Ground,
+            params: [0; MAX_PARAMS],
+            num_params: 0,
+        }
+    }
+
+    pub fn advance(&mut self, byte: u8) -> Action {
+        // ESC always starts a new sequence
+        if byte == ESCAPE {
+            self.state = State::Escape;
+            return Action::Nothing;
+        }
+
+        match self.state {
+            State::Ground => Action::Print(byte),
+            State::Escape => {
+                self.state = State::Ground;
+                match byte {
+                    b'[' => {
+                        self.state = State::Csi;
+                        self.params = [0; MAX_PARAMS];
+                        self.num_params = 0;
+                        Action::Nothing
+                    }
+                    b'7' => Action::SaveCursor,
+                    b'8' => Action::RestoreCursor,
+                    // Unsupported, drop it
+                    _ => Action::Nothing,
+                }
+            }
+            State::Csi => match byte {
+                b'0'..=b'9' => {
+                    if self.num_params == 0 {
+                        self.num_params = 1;
+                    }
+                    let param = &mut self.params[self.num_params - 1];
+                    *param = param.saturating_mul(10).saturating_add((byte - b'0') as u16);
+                    Action::Nothing
+                }
+                b';' => {
+                    if self.num_params == 0 {
+                        self.num_params = 1;
+                    }
+                    if self.num_params < MAX_PARAMS {
+                        self.num_params += 1;
+                    }
+                    Action::Nothing
+                }
+                // Final byte
+                0x40..=0x7e => {
+                    self.state = State::Ground;
+                    Action::Csi(Csi {
+                        params: self.params,
+                        num_params: self.num_params,
+                        final_byte: byte,
+                    })
+                }
+                // Control characters still work in the middle of a sequence
+                0x00..=0x1f => Action::Print(byte),
+                // Private markers and intermediate bytes are ignored
+                _ => Action::Nothing,
+            },
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn feed(parser: &mut Parser, bytes: &[u8]) -> Action {
+        let mut action = Action::Nothing;
+        for &byte in bytes {
+            action = parser.advance(byte);
+        }
+        action
+    }
+
+    #[test_case]
+    fn plain_bytes_are_printed() {
+        let mut parser = Parser::new();
+        assert_eq!(parser.advance(b'a'), Action::Print(b'a'));
+    }
+
+    #[test_case]
+    fn csi_params_are_parsed() {
+        let mut parser = Parser::new();
+        match feed(&mut parser, b"\x1b[12;;5H") {
+            Action::Csi(csi) => {
+                assert_eq!(csi.final_byte, b'H');
+                assert_eq!(csi.params(), &[12, 0, 5]);
+                assert_eq!(csi.param_or(1, 1), 1);
+            }
+            action => panic!("Expected a CSI, got {:?}", action),
+        }
+        assert_eq!(parser.advance(b'x'), Action::Print(b'x'));
+    }
+}
@@ -0,0 +1,95 @@
+// Translation from Unicode to code page 437, the character set of the VGA
+// text mode font
+
+// The glyphs of 0x01 to 0x1f, these bytes are control characters in ASCII
+const LOW_GLYPHS: [char; 31] = [
+    '☺', '☻', '♥', '♦', '♣', '♠', '•', '◘',
+    '○', '◙', '♂', '♀', '♪', '♫', '☼', '►',
+    '◄', '↕', '‼', '¶', '§', '▬', '↨', '↑',
+    '↓', '→', '←', '∟', '↔', '▲', '▼',
+];
+
+// The glyphs of 0x80 to 0xff
+const HIGH_GLYPHS: [char; 128] = [
+    'Ç', 'ü', 'é', 'â', 'ä', 'à', 'å', 'ç',
+    'ê', 'ë', 'è', 'ï', 'î', 'ì', 'Ä', 'Å',
+    'É', 'æ', 'Æ', 'ô', 'ö', 'ò', 'û', 'ù',
+    'ÿ', 'Ö', 'Ü', '¢', '£', '¥', '₧', 'ƒ',
+    'á', 'í', 'ó', 'ú', 'ñ', 'Ñ', 'ª', 'º',
+    '¿', '⌐', '¬', '½', '¼', '¡', '«', '»',
+    '░', '▒', '▓', '│', '┤', '╡', '╢', '╖',
+    '╕', '╣', '║', '╗', '╝', '╜', '╛', '┐',
+    '└', '┴', '┬', '├', '─', '┼', '╞', '╟',
+    '╚', '╔', '╩', '╦', '╠', '═', '╬', '╧',
+    '╨', '╤', '╥', '╙', '╘', '╒', '╓', '╫',
+    '╪', '┘', '┌', '█', '▄', '▌', '▐', '▀',
+    'α', 'ß', 'Γ', 'π', 'Σ', 'σ', 'µ', 'τ',
+    'Φ', 'Θ', 'Ω', 'δ', '∞', 'φ', 'ε', '∩',
+    '≡', '±', '≥', '≤', '⌠', '⌡', '÷', '≈',
+    '°', '∙', '·', '√', 'ⁿ', '²', '■', '\u{a0}',
+];
+
+// Accented letters that aren't in code page 437 and the letter they are shown as
+const FALLBACKS: [(char, u8); 24] = [
+    ('À', b'A'), ('Á', b'A'), ('Â', b'A'), ('Ã', b'A'), ('È', b'E'), ('Ê', b'E'),
+    ('Ë', b'E'), ('Ì', b'I'), ('Í', b'I'), ('Î', b'I'), ('Ï', b'I'), ('Ò', b'O'),
+    ('Ó', b'O'), ('Ô', b'O'), ('Õ', b'O'), ('Ù', b'U'), ('Ú', b'U'), ('Û', b'U'),
+    ('Ý', b'Y'), ('ã', b'a'), ('õ', b'o'), ('ý', b'y'), ('Ÿ', b'Y'), ('ĳ', b'y'),
+];
+
+// The code page 437 byte showing `c`, printable ASCII maps to itself
+pub fn from_char(c: char) -> Option<u8> {
+    if (' '..='~').contains(&c) {
+        return Some(c as u8);
+    }
+    if c == '⌂' {
+        return Some(0x7f);
+    }
+    if let Some(index) = LOW_GLYPHS.iter().position(|&glyph| glyph == c) {
+        return Some(index as u8 + 0x01);
+    }
+    if let Some(index) = HIGH_GLYPHS.iter().position(|&glyph| glyph == c) {
+        return Some(index as u8 + 0x80);
+    }
+    FALLBACKS.iter()
+        .find(|&&(letter, _)| letter == c)
+        .map(|&(_, byte)| byte)
+}
+
+// The Unicode character shown for a code page 437 byte
+pub fn to_char(byte: u8) -> char {
+    match byte {
+        0x01..=0x1f => LOW_GLYPHS[(byte - 0x01) as usize],
+        0x7f => '⌂',
+        0x80..=0xff => HIGH_GLYPHS[(byte - 0x80) as usize],
+        _ => byte as char,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test_case]
+    fn ascii_maps_to_itself() {
+        assert_eq!(from_char('a'), Some(b'a'));
+        assert_eq!(from_char('~'), Some(b'~'));
+    }
+
+    #[test_case]
+    fn glyphs_map_to_code_page_437() {
+        assert_eq!(from_char('☺'), Some(0x01));
+        assert_eq!(from_char('é'), Some(0x82));
+        assert_eq!(from_char('─'), Some(0xc4));
+        assert_eq!(from_char('▓'), Some(0xb2));
+        assert_eq!(from_char('Ë'), Some(b'E'));
+        assert_eq!(from_char('€'), None);
+    }
+
+    #[test_case]
+    fn to_char_reverses_from_char() {
+        for byte in 0x01..=0xffu8 {
+            assert_eq!(from_char(to_char(byte)), Some(byte));
+        }
+    }
+}
@@ -0,0 +1,224 @@
+// A text console drawn with the built-in font on the linear framebuffer that
+// the bootloader set up, used instead of VGA text mode when there is one
+
+use alloc::vec;
+use alloc::vec::Vec;
+use multiboot2::{FramebufferTag, FramebufferType};
+
+use crate::memory;
+use super::font::{FONT, GLYPH_HEIGHT, GLYPH_WIDTH};
+use super::{Color, ColorCode, ScreenChar};
+
+// RGB values of the 16 VGA colours, in the order of the Color enum
+pub(crate) const VGA_PALETTE: [(u8, u8, u8); 16] = [
+    (0x00, 0x00, 0x00), (0x00, 0x00, 0xaa), (0x00, 0xaa, 0x00), (0x00, 0xaa, 0xaa),
+    (0xaa, 0x00, 0x00), (0xaa, 0x00, 0xaa), (0xaa, 0x55, 0x00), (0xaa, 0xaa, 0xaa),
+    (0x55, 0x55, 0x55), (0x55, 0x55, 0xff), (0x55, 0xff, 0x55), (0x55, 0xff, 0xff),
+    (0xff, 0x55, 0x55), (0xff, 0x55, 0xff), (0xff, 0xff, 0x55), (0xff, 0xff, 0xff),
+];
+
+// The cursor covers the last two scanlines of a cell, like the VGA one
+const CURSOR_START: usize = GLYPH_HEIGHT - 2;
+
+pub struct FramebufferConsole {
+    pixels: &'static mut [u8],
+    pitch: usize,            // Bytes per line of pixels
+    bytes_per_pixel: usize,
+    pixel_width: usize,
+    pixel_height: usize,
+    color_fields: [(u8, u8); 3], // Position and size of red, green and blue
+    width: usize,            // In characters
+    height: usize,
+    colors: [u32; 16],       // The VGA colours as pixel values
+    cells: Vec<ScreenChar>,  // What is on the screen, so it can be read back
+    cursor: (usize, usize),  // Row and column
+    cursor_visible: bool,
+    text_hidden: bool,       // Graphics are shown, the cells are only remembered
+}
+
+impl FramebufferConsole {
+    // Returns None for framebuffers we can't draw on (text mode, palettes and
+    // other depths than 24 or 32 bits), needs the heap
+    pub fn new(tag: &FramebufferTag) -> Option<FramebufferConsole> {
+        let (red, green, blue) = match &tag.buffer_type {
+            FramebufferType::RGB { red, green, blue } => (red, green, blue),
+            _ => return None,
+        };
+        let bytes_per_pixel = match tag.bpp {
+            24 => 3,
+            32 => 4,
+            _ => return None,
+        };
+
+        let size = tag.pitch as usize * tag.height as usize;
+        memory::identity_map(tag.address, size as u64);
+
+        let color_fields = [
+            (red.position, red.size),
+            (green.position, green.size),
+            (blue.position, blue.size),
+        ];
+        let mut colors = [0; 16];
+        for (color, &rgb) in colors.iter_mut().zip(VGA_PALETTE.iter()) {
+            *color = pixel_value(color_fields, rgb);
+        }
+
+        let width = tag.width as usize / GLYPH_WIDTH;
+        let height = tag.height as usize / GLYPH_HEIGHT;
+        let blank = ScreenChar {
+            ascii_character: b' ',
+            color_code: ColorCode::new(Color::LightGray, Color::Black),
+        };
+        let mut console = FramebufferConsole {
+            pixels: unsafe { core::slice::from_raw_parts_mut(tag.address as *mut u8, size) },
+            pitch: tag.pitch as usize,
+            bytes_per_pixel,
+            pixel_width: tag.width as usize,
+            pixel_height: tag.height as usize,
+            color_fields,
+            width,
+            height,
+            colors,
+            cells: vec![blank; width * height],
+            cursor: (0, 0),
+            cursor_visible: false,
+            text_hidden: false,
+        };
+        console.draw_all_cells();
+        Some(console)
+    }
+
+    pub fn width(&self) -> usize {
+        self.width
+    }
+
+    pub fn height(&self) -> usize {
+        self.height
+    }
+
+    pub fn read(&self, row: usize, col: usize) -> ScreenChar {
+        self.cells[row * self.width + col]
+    }
+
+    pub fn write(&mut self, row: usize, col: usize, character: ScreenChar) {
+        let index = row * self.width + col;
+        if self.cells[index] != character {
+            self.cells[index] = character;
+            self.draw_cell(row, col);
+        }
+    }
+
+    // Move the rows below top up one, the last row keeps its contents. The
+    // pixels are moved as well instead of drawing every cell again.
+    pub fn scroll_up(&mut self, top: usize) {
+        let (row, col) = self.cursor;
+        let visible = self.cursor_visible;
+        self.cursor_visible = false;
+        self.draw_cell(row, col);
+
+        self.cells.copy_within((top + 1) * self.width.., top * self.width);
+        if !self.text_hidden {
+            let row_bytes = self.pitch * GLYPH_HEIGHT;
+            self.pixels.copy_within((top + 1) * row_bytes..row_bytes * self.height, top * row_bytes);
+        }
+
+        self.cursor_visible = visible;
+        self.draw_cell(row, col);
+    }
+
+    pub fn show_cursor(&mut self) {
+        self.cursor_visible = true;
+        let (row, col) = self.cursor;
+        self.draw_cell(row, col);
+    }
+
+    pub fn move_cursor(&mut self, row: usize, col: usize) {
+        let (old_row, old_col) = self.cursor;
+        self.cursor = (row, col);
+        if self.cursor_visible && (old_row, old_col) != (row, col) {
+            self.draw_cell(old_row, old_col);
+            self.draw_cell(row, col);
+        }
+    }
+
+    pub fn pixel_width(&self) -> usize {
+        self.pixel_width
+    }
+
+    pub fn pixel_height(&self) -> usize {
+        self.pixel_height
+    }
+
+    // Stop drawing the text so the pixels can be used for graphics, the
+    // cells are still kept up to date
+    pub fn hide_text(&mut self) {
+        self.text_hidden = true;
+    }
+
+    pub fn show_text(&mut self) {
+        self.text_hidden = false;
+        self.draw_all_cells();
+    }
+
+    // Fill a rectangle of pixels with an RGB colour, it is clipped to the screen
+    pub fn fill_rect(&mut self, x: usize, y: usize, width: usize, height: usize, rgb: (u8, u8, u8)) {
+        let color = pixel_value(self.color_fields, rgb);
+        let x_end = core::cmp::min(x + width, self.pixel_width);
+        let y_end = core::cmp::min(y + height, self.pixel_height);
+        for pixel_y in y..y_end {
+            for pixel_x in x..x_end {
+                self.put_pixel(pixel_x, pixel_y, color);
+            }
+        }
+    }
+
+    fn draw_all_cells(&mut self) {
+        for row in 0..self.height {
+            for col in 0..self.width {
+                self.draw_cell(row, col);
+            }
+        }
+    }
+
+    fn draw_cell(&mut self, row: usize, col: usize) {
+        if self.text_hidden {
+            return;
+        }
+        let cell = self.cells[row * self.width + col];
+        let foreground = self.colors[(cell.color_code.0 & 0x0f) as usize];
+        let background = self.colors[(cell.color_code.0 >> 4) as usize];
+        let has_cursor = self.cursor_visible && self.cursor == (row, col);
+
+        for (y, &glyph_row) in FONT[cell.ascii_character as usize].iter().enumerate() {
+            let bits = if has_cursor && y >= CURSOR_START { 0xff } else { glyph_row };
+            for x in 0..GLYPH_WIDTH {
+                let color = if bits & (0x80 >> x) != 0 { foreground } else { background };
+                self.put_pixel(col * GLYPH_WIDTH + x, row * GLYPH_HEIGHT + y, color);
+            }
+        }
+    }
+
+    fn put_pixel(&mut self, x: usize, y: usize, color: u32) {
+        let offset = y * self.pitch + x * self.bytes_per_pixel;
+        let bytes = color.to_le_bytes();
+        self.pixels[offset..offset + self.bytes_per_pixel]
+            .copy_from_slice(&bytes[..self.bytes_per_pixel]);
+    }
+}
+
+// An RGB colour in the pixel format of the framebuffer
+fn pixel_value(color_fields: [(u8, u8); 3], (r, g, b): (u8, u8, u8)) -> u32 {
+    pixel_component(r, color_fields[0]) |
+        pixel_component(g, color_fields[1]) |
+        pixel_component(b, color_fields[2])
+}
+
+// An 8-bit colour component scaled to the size of the field and moved to its
+// position in the pixel
+fn pixel_component(value: u8, (position, size): (u8, u8)) -> u32 {
+    if size == 0 {
+        return 0;
+    }
+    let size = if size > 8 { 8 } else { size };
+    ((value >> (8 - size)) as u32) << position
+}
@@ -0,0 +1,889 @@
+use lazy_static::lazy_static; // So we can have a static WRITER
+use spin::Mutex;              // So the static WRITER can't have data races
+use volatile::Volatile;       // For the Buffer
+use core::fmt;                // For the write! and writeln! macros
+use alloc::collections::VecDeque; // For the scrollback history
+use alloc::vec;
+use alloc::vec::Vec;
+use x86_64::instructions::port::Port; // For the hardware cursor
+use multiboot2::BootInformation;
+
+use crate::serial;            // To mirror everything to COM1
+use crate::serial::SERIAL1;
+
+mod ansi;
+mod cp437;
+mod font;
+mod framebuffer;
+
+use self::ansi::{Action, Csi};
+pub use self::framebuffer::FramebufferConsole;
+pub(crate) use self::framebuffer::VGA_PALETTE;
+
+// "Static" Writer instance for writing globally
+lazy_static! {
+    pub static ref WRITER: Mutex<Writer> = Mutex::new(Writer::new(
+        Display::Text(unsafe { &mut *(0xb8000 as *mut Buffer) })
+    ));
+}
+
+#[allow(dead_code)]                          // Some are unused and that is ok
+#[derive(Debug, Clone, Copy, PartialEq, Eq)] // Derive the implementations
+#[repr(u8)]                                  // Store them as a u8 (u4 does not exist)
+pub enum Color {
+    Black = 0,
+    Blue = 1,
+    Green = 2,
+    Cyan = 3,
+    Red = 4,
+    Magenta = 5,
+    Brown = 6,
+    LightGray = 7,
+    DarkGray = 8,
+    LightBlue = 9,
+    LightGreen = 10,
+    LightCyan = 11,
+    LightRed = 12,
+    Pink = 13,
+    Yellow = 14,
+    White = 15,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)] // Derive the implementations
+#[repr(transparent)]                         // Use the same data layout as u8
+struct ColorCode(u8);
+
+// ANSI colour numbers (30-37 and 90-97 for the foreground) to VGA colours
+const ANSI_COLORS: [Color; 16] = [
+    Color::Black, Color::Red, Color::Green, Color::Brown,
+    Color::Blue, Color::Magenta, Color::Cyan, Color::LightGray,
+    Color::DarkGray, Color::LightRed, Color::LightGreen, Color::Yellow,
+    Color::LightBlue, Color::Pink, Color::LightCyan, Color::White,
+];
+
+impl ColorCode {
+    fn new(foreground: Color, background: Color) -> ColorCode {
+        ColorCode((background as u8) << 4 | (foreground as u8))
+    }
+
+    fn with_foreground(self, foreground: Color) -> ColorCode {
+        ColorCode((self.0 & 0xf0) | (foreground as u8))
+    }
+
+    fn with_background(self, background: Color) -> ColorCode {
+        ColorCode((background as u8) << 4 | (self.0 & 0x0f))
+    }
+
+    // Bright colours are the normal ones with bit 3 set
+    fn brightened(self) -> ColorCode {
+        ColorCode(self.0 | 0x08)
+    }
+
+    fn dimmed(self) -> ColorCode {
+        ColorCode(self.0 & !0x08)
+    }
+
+    // Foreground and background swapped
+    fn inverted(self) -> ColorCode {
+        ColorCode(self.0.rotate_left(4))
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)] // Derive the implementations
+#[repr(C)]                                   // Make sure fields aren't ordered differently
+struct ScreenChar {
+    ascii_character: u8,
+    color_code: ColorCode,
+}
+
+impl ScreenChar {
+    fn inverted(self) -> ScreenChar {
+        ScreenChar { color_code: self.color_code.inverted(), ..self }
+    }
+}
+
+const BUFFER_HEIGHT: usize = 25;
+const BUFFER_WIDTH: usize = 80;
+const SCROLLBACK_LINES: usize = 1000;
+const TAB_WIDTH: usize = 8;
+
+// CRT controller registers, for the hardware cursor
+const CRTC_INDEX_PORT: u16 = 0x3D4;
+const CRTC_DATA_PORT: u16 = 0x3D5;
+const CURSOR_START_REGISTER: u8 = 0x0A;
+const CURSOR_END_REGISTER: u8 = 0x0B;
+const CURSOR_LOCATION_HIGH_REGISTER: u8 = 0x0E;
+const CURSOR_LOCATION_LOW_REGISTER: u8 = 0x0F;
+
+#[repr(transparent)]                         // Use the data layout from its single field
+struct Buffer {
+    chars: [[Volatile<ScreenChar>; BUFFER_WIDTH]; BUFFER_HEIGHT],
+}
+
+// Where the characters are shown: VGA text memory at 0xb8000, or a pixel
+// console when the bootloader gave us a framebuffer
+enum Display {
+    Text(&'static mut Buffer),
+    Framebuffer(FramebufferConsole),
+}
+
+impl Display {
+    fn width(&self) -> usize {
+        match self {
+            Display::Text(_) => BUFFER_WIDTH,
+            Display::Framebuffer(console) => console.width(),
+        }
+    }
+
+    fn height(&self) -> usize {
+        match self {
+            Display::Text(_) => BUFFER_HEIGHT,
+            Display::Framebuffer(console) => console.height(),
+        }
+    }
+
+    fn read(&self, row: usize, col: usize) -> ScreenChar {
+        match self {
+            Display::Text(buffer) => buffer.chars[row][col].read(),
+            Display::Framebuffer(console) => console.read(row, col),
+        }
+    }
+
+    fn write(&mut self, row: usize, col: usize, character: ScreenChar) {
+        match self {
+            Display::Text(buffer) => buffer.chars[row][col].write(character),
+            Display::Framebuffer(console) => console.write(row, col, character),
+        }
+    }
+
+    // Move the rows below top up one, the last row keeps its contents
+    fn scroll_up(&mut self, top: usize) {
+        match self {
+            Display::Text(buffer) => {
+                for row in top + 1..BUFFER_HEIGHT {
+                    for col in 0..BUFFER_WIDTH {
+                        let character = buffer.chars[row][col].read();
+                        buffer.chars[row - 1][col].write(character);
+                    }
+                }
+            }
+            Display::Framebuffer(console) => console.scroll_up(top),
+        }
+    }
+
+    fn show_cursor(&mut self) {
+        match self {
+            Display::Text(_) => {
+                let mut index_port: Port<u8> = Port::new(CRTC_INDEX_PORT);
+                let mut data_port: Port<u8> = Port::new(CRTC_DATA_PORT);
+                unsafe {
+                    // Clearing bit 5 of the start register shows the cursor, it
+                    // covers scanlines 14 and 15 (an underline)
+                    index_port.write(CURSOR_START_REGISTER);
+                    let start = data_port.read();
+                    data_port.write((start & 0xC0) | 14);
+                    index_port.write(CURSOR_END_REGISTER);
+                    let end = data_port.read();
+                    data_port.write((end & 0xE0) | 15);
+                }
+            }
+            Display::Framebuffer(console) => console.show_cursor(),
+        }
+    }
+
+    fn move_cursor(&mut self, row: usize, col: usize) {
+        match self {
+            Display::Text(_) => {
+                let position = row * BUFFER_WIDTH + col;
+
+                let mut index_port: Port<u8> = Port::new(CRTC_INDEX_PORT);
+                let mut data_port: Port<u8> = Port::new(CRTC_DATA_PORT);
+                unsafe {
+                    index_port.write(CURSOR_LOCATION_LOW_REGISTER);
+                    data_port.write((position & 0xFF) as u8);
+                    index_port.write(CURSOR_LOCATION_HIGH_REGISTER);
+                    data_port.write(((position >> 8) & 0xFF) as u8);
+                }
+            }
+            Display::Framebuffer(console) => console.move_cursor(row, col),
+        }
+    }
+}
+
+// Starts at the bottom line and writes until the end of the line
+// shifts everything up on a newline or at the end of a line (like a typewriter)
+// After a form feed it starts at the top again and moves down until the bottom
+pub struct Writer {
+    column_position: usize,
+    row_position: usize,
+    first_row: usize,            // 1 when the top row is the status bar
+    saved_position: (usize, usize), // Row and column for ESC 7 / ESC [ s
+    color_code: ColorCode,
+    bold: bool,                  // Bold text is shown in the bright colours
+    ansi_parser: ansi::Parser,   // Escape sequences can be split over multiple writes
+    display: Display,            // VGA text memory or the framebuffer console
+    cursor_enabled: bool,        // Kept when switching to the framebuffer
+    scrollback: Option<VecDeque<Vec<ScreenChar>>>, // Lines that scrolled off the top, oldest first
+    scroll_offset: usize,        // How many lines the view is scrolled back, 0 is the live screen
+    live_screen: Vec<Vec<ScreenChar>>, // The live screen while scrolled back
+    mouse_cell: Option<(usize, usize)>, // Row and column of the mouse cursor, shown inverted
+}
+
+impl Writer {
+    fn new(display: Display) -> Writer {
+        Writer {
+            column_position: 0,
+            row_position: BUFFER_HEIGHT - 1,
+            first_row: 0,
+            saved_position: (BUFFER_HEIGHT - 1, 0),
+            color_code: ColorCode::new(Color::LightGray, Color::Black),
+            bold: false,
+            ansi_parser: ansi::Parser::new(),
+            display,
+            cursor_enabled: false,
+            scrollback: None,
+            scroll_offset: 0,
+            live_screen: Vec::new(),
+            mouse_cell: None,
+        }
+    }
+
+    // Switch to the framebuffer if the bootloader set up one we can draw on,
+    // needs the heap. The screen starts out empty.
+    pub fn use_framebuffer(&mut self, boot_info: &BootInformation) {
+        let console = match boot_info.framebuffer_tag().and_then(|tag| FramebufferConsole::new(&tag)) {
+            Some(console) => console,
+            None => return,
+        };
+        self.display = Display::Framebuffer(console);
+        self.row_position = self.height() - 1;
+        self.column_position = 0;
+        self.saved_position = (self.row_position, 0);
+        if self.cursor_enabled {
+            self.enable_cursor();
+        }
+    }
+
+    // The framebuffer console, None in VGA text mode
+    pub fn framebuffer(&mut self) -> Option<&mut FramebufferConsole> {
+        match &mut self.display {
+            Display::Framebuffer(console) => Some(console),
+            Display::Text(_) => None,
+        }
+    }
+
+    pub fn width(&self) -> usize {
+        self.display.width()
+    }
+
+    pub fn height(&self) -> usize {
+        self.display.height()
+    }
+
+    // Row and column of the next character, the column is the width after the
+    // last column of a row is written
+    pub fn cursor_position(&self) -> (usize, usize) {
+        (self.row_position, self.column_position)
+    }
+
+    // Start keeping the lines that scroll off the top, needs the heap. Call it
+    // after use_framebuffer, the copy of the live screen is allocated here for
+    // the size of the display because scrolling happens in the keyboard
+    // interrupt, which must not allocate.
+    pub fn enable_scrollback(&mut self) {
+        self.scrollback = Some(VecDeque::new());
+        let blank = ScreenChar {
+            ascii_character: b' ',
+            color_code: self.color_code,
+        };
+        self.live_screen = vec![vec![blank; self.width()]; self.height()];
+    }
+
+    pub fn write_byte(&mut self, byte: u8) {
+        self.snap_to_bottom();
+        match self.ansi_parser.advance(byte) {
+            Action::Print(byte) => self.write_plain_byte(byte),
+            Action::Csi(csi) => self.execute_csi(&csi),
+            Action::SaveCursor => {
+                self.saved_position = (self.row_position, self.column_position);
+            }
+            Action::RestoreCursor => {
+                let (row, col) = self.saved_position;
+                self.row_position = row;
+                self.column_position = col;
+            }
+            Action::Nothing => (),
+        }
+        self.update_cursor();
+    }
+
+    // A byte that isn't part of an escape sequence
+    fn write_plain_byte(&mut self, byte: u8) {
+        match byte {
+            b'\n' => self.new_line(),
+            b'\r' => self.column_position = 0,
+            // Backspace only moves back, "\x08 \x08" erases
+            0x08 => {
+                if self.column_position > 0 {
+                    self.column_position -= 1;
+                }
+            }
+            b'\t' => {
+                if self.column_position >= self.width() {
+                    self.new_line();
+                }
+                let next_stop = (self.column_position / TAB_WIDTH + 1) * TAB_WIDTH;
+                while self.column_position < next_stop && self.column_position < self.width() {
+                    self.put_byte(b' ');
+                }
+            }
+            // Form feed clears the screen
+            0x0c => self.clear_screen(),
+            byte => self.put_glyph(byte),
+        }
+    }
+
+    // Show a code page 437 byte as its glyph, even the bytes that are control
+    // characters in ASCII
+    pub fn write_glyph(&mut self, byte: u8) {
+        self.snap_to_bottom();
+        self.put_glyph(byte);
+        self.update_cursor();
+    }
+
+    // A byte from a program in raw output mode, only the control characters we
+    // handle and printable ASCII go through write_byte
+    pub fn write_raw_byte(&mut self, byte: u8) {
+        if is_handled_byte(byte) {
+            self.write_byte(byte);
+        } else {
+            self.write_glyph(byte);
+        }
+    }
+
+    fn put_glyph(&mut self, byte: u8) {
+        if self.column_position >= self.width() {
+            self.new_line();
+        }
+        self.put_byte(byte);
+    }
+
+    fn execute_csi(&mut self, csi: &Csi) {
+        let count = csi.param_or(0, 1) as usize;
+        match csi.final_byte {
+            // Cursor position, 1-based
+            b'H' | b'f' => {
+                let row = csi.param_or(0, 1) as usize;
+                let col = csi.param_or(1, 1) as usize;
+                self.row_position = self.first_row + clamp(row - 1, self.height() - 1 - self.first_row);
+                self.column_position = clamp(col - 1, self.width() - 1);
+            }
+            // Cursor up, down, forward and back
+            b'A' => {
+                let row = self.row_position.saturating_sub(count);
+                self.row_position = if row < self.first_row { self.first_row } else { row };
+            }
+            b'B' => self.row_position = clamp(self.row_position + count, self.height() - 1),
+            b'C' => self.column_position = clamp(self.column_position + count, self.width() - 1),
+            b'D' => {
+                let col = clamp(self.column_position, self.width() - 1);
+                self.column_position = col.saturating_sub(count);
+            }
+            // Cursor to column
+            b'G' => self.column_position = clamp(count - 1, self.width() - 1),
+            // Erase in display
+            b'J' => {
+                let row = self.row_position;
+                let col = clamp(self.column_position, self.width() - 1);
+                match csi.param_or(0, 0) {
+                    0 => {
+                        self.clear_cells(row, col, self.width());
+                        for below in row + 1..self.height() {
+                            self.clear_row(below);
+                        }
+                    }
+                    1 => {
+                        for above in self.first_row..row {
+                            self.clear_row(above);
+                        }
+                        self.clear_cells(row, 0, col + 1);
+                    }
+                    2 => {
+                        for row in self.first_row..self.height() {
+                            self.clear_row(row);
+                        }
+                    }
+                    _ => (),
+                }
+            }
+            // Erase in line
+            b'K' => {
+                let row = self.row_position;
+                let col = clamp(self.column_position, self.width() - 1);
+                match csi.param_or(0, 0) {
+                    0 => self.clear_cells(row, col, self.width()),
+                    1 => self.clear_cells(row, 0, col + 1),
+                    2 => self.clear_row(row),
+                    _ => (),
+                }
+            }
+            b'm' => self.select_graphic_rendition(csi),
+            b's' => self.saved_position = (self.row_position, self.column_position),
+            b'u' => {
+                let (row, col) = self.saved_position;
+                self.row_position = row;
+                self.column_position = col;
+            }
+            // Unsupported, ignore it
+            _ => (),
+        }
+    }
+
+    // SGR: ESC [ ... m sets the colours
+    fn select_graphic_rendition(&mut self, csi: &Csi) {
+        // No parameters is the same as a reset
+        if csi.params().is_empty() {
+            self.reset_colors();
+        }
+        for &param in csi.params() {
+            match param {
+                0 => self.reset_colors(),
+                1 => {
+                    self.bold = true;
+                    self.color_code = self.color_code.brightened();
+                }
+                22 => {
+                    self.bold = false;
+                    self.color_code = self.color_code.dimmed();
+                }
+                30..=37 => {
+                    let color = ANSI_COLORS[(param - 30) as usize + if self.bold { 8 } else { 0 }];
+                    self.color_code = self.color_code.with_foreground(color);
+                }
+                39 => {
+                    let color = if self.bold { Color::White } else { Color::LightGray };
+                    self.color_code = self.color_code.with_foreground(color);
+                }
+                40..=47 => {
+                    let color = ANSI_COLORS[(param - 40) as usize];
+                    self.color_code = self.color_code.with_background(color);
+                }
+                49 => self.color_code = self.color_code.with_background(Color::Black),
+                90..=97 => {
+                    let color = ANSI_COLORS[(param - 90) as usize + 8];
+                    self.color_code = self.color_code.with_foreground(color);
+                }
+                100..=107 => {
+                    let color = ANSI_COLORS[(param - 100) as usize + 8];
+                    self.color_code = self.color_code.with_background(color);
+                }
+                // Unsupported, ignore it
+                _ => (),
+            }
+        }
+    }
+
+    fn reset_colors(&mut self) {
+        self.bold = false;
+        self.color_code = ColorCode::new(Color::LightGray, Color::Black);
+    }
+
+    // Put a byte at the cursor and move the cursor right
+    fn put_byte(&mut self, byte: u8) {
+        let row = self.row_position;
+        let col = self.column_position;
+
+        let color_code = self.color_code;
+        self.write_cell(row, col, ScreenChar {
+            ascii_character: byte,
+            color_code,
+        });
+        self.column_position += 1;
+    }
+
+    fn new_line(&mut self) {
+        self.column_position = 0;
+        if self.row_position < self.height() - 1 {
+            self.row_position += 1;
+            return;
+        }
+
+        if self.scrollback.is_some() {
+            let top = self.first_row;
+            let line = (0..self.width()).map(|col| self.read_cell(top, col)).collect();
+            if let Some(scrollback) = &mut self.scrollback {
+                if scrollback.len() == SCROLLBACK_LINES {
+                    scrollback.pop_front();
+                }
+                scrollback.push_back(line);
+            }
+        }
+
+        // The mouse cursor stays where it is instead of moving up
+        let mouse_cell = self.mouse_cell;
+        self.set_mouse_cursor(None);
+        self.display.scroll_up(self.first_row);
+        self.set_mouse_cursor(mouse_cell);
+        self.clear_row(self.height() - 1);
+    }
+
+    // Blank the columns from start up to (not including) end
+    fn clear_cells(&mut self, row: usize, start: usize, end: usize) {
+        let blank = ScreenChar {
+            ascii_character: b' ',
+            color_code: self.color_code,
+        };
+        for col in start..end {
+            self.write_cell(row, col, blank);
+        }
+    }
+
+    fn clear_screen(&mut self) {
+        for row in self.first_row..self.height() {
+            self.clear_row(row);
+        }
+        self.row_position = self.first_row;
+        self.column_position = 0;
+    }
+
+    pub fn enable_cursor(&mut self) {
+        self.cursor_enabled = true;
+        self.display.show_cursor();
+        self.update_cursor();
+    }
+
+    // Move the cursor to where the next byte goes
+    fn update_cursor(&mut self) {
+        let col = if self.column_position < self.width() {
+            self.column_position
+        } else {
+            self.width() - 1
+        };
+        self.display.move_cursor(self.row_position, col);
+    }
+
+    fn clear_row(&mut self, row: usize) {
+        self.clear_cells(row, 0, self.width());
+    }
+
+    pub fn write_string(&mut self, s: &str) {
+        for c in s.chars() {
+            if c.is_ascii() && is_handled_byte(c as u8) {
+                self.write_byte(c as u8);
+            } else {
+                // the code page 437 glyph, or a square if there is none
+                self.write_glyph(cp437::from_char(c).unwrap_or(0xfe));
+            }
+        }
+    }
+
+    pub fn scroll_up(&mut self, lines: usize) {
+        let history = match &self.scrollback {
+            Some(scrollback) => scrollback.len(),
+            None => return,
+        };
+        if self.scroll_offset == 0 {
+            // Remember the live screen so it can be put back
+            for row in self.first_row..self.height() {
+                for col in 0..self.width() {
+                    let character = self.read_cell(row, col);
+                    self.live_screen[row - self.first_row][col] = character;
+                }
+            }
+        }
+        self.scroll_offset += lines;
+        if self.scroll_offset > history {
+            self.scroll_offset = history;
+        }
+        self.render_view();
+    }
+
+    pub fn scroll_down(&mut self, lines: usize) {
+        if lines >= self.scroll_offset {
+            self.snap_to_bottom();
+        } else {
+            self.scroll_offset -= lines;
+            self.render_view();
+        }
+    }
+
+    // Show the live screen again
+    fn snap_to_bottom(&mut self) {
+        if self.scroll_offset == 0 {
+            return;
+        }
+        self.scroll_offset = 0;
+        self.render_view();
+    }
+
+    // Draw the screen as seen scroll_offset lines back into the history
+    fn render_view(&mut self) {
+        let scrollback = match &self.scrollback {
+            Some(scrollback) => scrollback,
+            None => return,
+        };
+        let first_line = scrollback.len() - self.scroll_offset;
+        // The lines are borrowed from self, so write_cell can't be used
+        let mouse_cell = self.mouse_cell;
+        for row in self.first_row..self.height() {
+            let line_index = first_line + row - self.first_row;
+            let line = if line_index < scrollback.len() {
+                &scrollback[line_index]
+            } else {
+                &self.live_screen[line_index - scrollback.len()]
+            };
+            for col in 0..self.width() {
+                let character = if mouse_cell == Some((row, col)) { line[col].inverted() } else { line[col] };
+                self.display.write(row, col, character);
+            }
+        }
+    }
+
+    // Keep the top row for the status bar, the rest scrolls beneath it
+    pub fn reserve_status_row(&mut self) {
+        self.first_row = 1;
+        if self.row_position == 0 {
+            self.row_position = 1;
+        }
+        if self.saved_position.0 == 0 {
+            self.saved_position.0 = 1;
+        }
+    }
+
+    // Draw the status bar in its own colours, the cursor stays where it is
+    pub fn set_status(&mut self, text: &str) {
+        if self.first_row == 0 {
+            return;
+        }
+        let color_code = ColorCode::new(Color::Black, Color::LightGray);
+        let mut chars = text.chars();
+        for col in 0..self.width() {
+            let byte = chars.next().map_or(b' ', |c| cp437::from_char(c).unwrap_or(0xfe));
+            self.write_cell(0, col, ScreenChar { ascii_character: byte, color_code });
+        }
+    }
+
+    // Show the mouse cursor as an inverted cell, None hides it
+    pub fn set_mouse_cursor(&mut self, cell: Option<(usize, usize)>) {
+        if cell == self.mouse_cell {
+            return;
+        }
+        for &(row, col) in self.mouse_cell.iter().chain(cell.iter()) {
+            let character = self.display.read(row, col);
+            self.display.write(row, col, character.inverted());
+        }
+        self.mouse_cell = cell;
+    }
+
+    // What is in a cell, without the mouse cursor
+    fn read_cell(&self, row: usize, col: usize) -> ScreenChar {
+        let character = self.display.read(row, col);
+        if self.mouse_cell == Some((row, col)) {
+            character.inverted()
+        } else {
+            character
+        }
+    }
+
+    fn write_cell(&mut self, row: usize, col: usize, character: ScreenChar) {
+        if self.mouse_cell == Some((row, col)) {
+            self.display.write(row, col, character.inverted());
+        } else {
+            self.display.write(row, col, character);
+        }
+    }
+
+    pub fn put_char_at(&mut self, c: char, x: usize, y: usize) {
+        self.snap_to_bottom();
+        self.write_cell(x, y, ScreenChar {
+            ascii_character: c as u8,
+            color_code: ColorCode::new(Color::White, Color::Black)
+        });
+    }
+}
+
+// Printable ASCII, a control character we handle or the start of an escape
+// sequence
+fn is_handled_byte(byte: u8) -> bool {
+    matches!(byte, 0x20..=0x7e | b'\n' | b'\r' | b'\t' | 0x08 | 0x0c | 0x1b)
+}
+
+fn clamp(value: usize, max: usize) -> usize {
+    if value > max { max } else { value }
+}
+
+impl fmt::Write for Writer {
+    fn write_str(&mut self, s: &str) -> fmt::Result {
+        self.write_string(s);
+        Ok(())
+    }
+}
+
+// print!() macro
+#[macro_export]
+macro_rules! print {
+    ($($arg:tt)*) => ($crate::terminal::_print(format_args!($($arg)*)));
+}
+
+#[macro_export]
+macro_rules! println {
+    () => ($crate::print!("\n"));
+    ($($arg:tt)*) => ($crate::print!("{}\n", format_args!($($arg)*)));
+}
+
+#[doc(hidden)]
+pub fn _print(args: fmt::Arguments) {
+    use core::fmt::Write;
+    use x86_64::instructions::interrupts;
+
+    interrupts::without_interrupts(|| {
+        WRITER.lock().write_fmt(args).unwrap();
+        if serial::is_mirrored() {
+            SERIAL1.lock().write_fmt(args).unwrap();
+        }
+    });
+}
+
+// Print a code page 437 byte, the serial port gets the matching Unicode
+// character
+pub fn print_raw_byte(byte: u8) {
+    use core::fmt::Write;
+    use x86_64::instructions::interrupts;
+
+    interrupts::without_interrupts(|| {
+        WRITER.lock().write_raw_byte(byte);
+        if serial::is_mirrored() {
+            let c = if is_handled_byte(byte) { byte as char } else { cp437::to_char(byte) };
+            SERIAL1.lock().write_char(c).unwrap();
+        }
+    });
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use alloc::boxed::Box;
+
+    // A writer with a blank text buffer of its own, so a test doesn't depend
+    // on what the other tests printed
+    fn test_writer() -> Writer {
+        let buffer: &'static mut Buffer = Box::leak(Box::new(unsafe { core::mem::zeroed() }));
+        Writer::new(Display::Text(buffer))
+    }
+
+    fn row_starts_with(writer: &Writer, row: usize, s: &str) -> bool {
+        s.bytes().enumerate().all(|(col, byte)| {
+        writer.display.read(row, col).ascii_character == byte
+        })
+    }
+
+    #[test_case]
+    fn new_line_scrolls_up() {
+        let mut writer = test_writer();
+        writer.write_string("\nfirst\nsecond");
+        assert!(row_starts_with(&writer, writer.height() - 2, "first"));
+        assert!(row_starts_with(&writer, writer.height() - 1, "second"));
+    }
+
+    #[test_case]
+    fn long_line_wraps() {
+        let mut writer = test_writer();
+        writer.write_byte(b'\n');
+        for _ in 0..writer.width() {
+            writer.write_byte(b'a');
+        }
+        writer.write_byte(b'b');
+        assert_eq!(writer.display.read(writer.height() - 2, writer.width() - 1).ascii_character, b'a');
+        assert!(row_starts_with(&writer, writer.height() - 1, "b"));
+    }
+
+    #[test_case]
+    fn non_ascii_is_a_square() {
+        let mut writer = test_writer();
+        writer.write_string("\n\u{7f}");
+        assert_eq!(writer.display.read(writer.height() - 1, 0).ascii_character, 0xfe);
+    }
+
+    #[test_case]
+    fn unicode_is_shown_in_code_page_437() {
+        let mut writer = test_writer();
+        writer.write_string("\n┌é☺\n");
+        assert_eq!(writer.display.read(writer.height() - 2, 0).ascii_character, 0xda);
+        assert_eq!(writer.display.read(writer.height() - 2, 1).ascii_character, 0x82);
+        assert_eq!(writer.display.read(writer.height() - 2, 2).ascii_character, 0x01);
+    }
+
+    #[test_case]
+    fn raw_bytes_are_glyphs() {
+        let mut writer = test_writer();
+        for &byte in b"\n\x03\xb1a\n" {
+            writer.write_raw_byte(byte);
+        }
+        assert_eq!(writer.display.read(writer.height() - 2, 0).ascii_character, 0x03);
+        assert_eq!(writer.display.read(writer.height() - 2, 1).ascii_character, 0xb1);
+        assert_eq!(writer.display.read(writer.height() - 2, 2).ascii_character, b'a');
+    }
+
+    #[test_case]
+    fn scrolled_off_lines_can_be_viewed() {
+        let mut writer = test_writer();
+        writer.enable_scrollback();
+        writer.write_string("\nmarker");
+        for _ in 0..writer.height() {
+            writer.write_byte(b'\n');
+        }
+        writer.scroll_up(1);
+        assert!(row_starts_with(&writer, 0, "marker"));
+        writer.scroll_down(1);
+        assert_eq!(writer.scroll_offset, 0);
+        assert!(!row_starts_with(&writer, 0, "marker"));
+    }
+
+    #[test_case]
+    fn mouse_cursor_inverts_the_cell() {
+        let mut writer = test_writer();
+        let row = writer.height() - 1;
+        writer.write_string("\nab");
+        writer.set_mouse_cursor(Some((row, 1)));
+        let color_code = writer.read_cell(row, 0).color_code;
+        assert_eq!(writer.display.read(row, 1).color_code, color_code.inverted());
+        assert_eq!(writer.read_cell(row, 1).color_code, color_code);
+        writer.set_mouse_cursor(None);
+        assert_eq!(writer.display.read(row, 1).color_code, color_code);
+    }
+
+    #[test_case]
+    fn control_characters_move_the_cursor() {
+        let mut writer = test_writer();
+        writer.write_string("\nxyz\rab\x08c\td");
+        assert!(row_starts_with(&writer, writer.row_position, "ac      d"));
+    }
+
+    #[test_case]
+    fn form_feed_clears_the_screen() {
+        let mut writer = test_writer();
+        writer.write_string("\nsomething\x0cfirst");
+        assert!(row_starts_with(&writer, 0, "first"));
+        assert!(row_starts_with(&writer, writer.height() - 1, "         "));
+    }
+
+    #[test_case]
+    fn sgr_sets_the_colors() {
+        let mut writer = test_writer();
+        writer.write_string("\x1b[1;31;44m");
+        assert_eq!(writer.color_code, ColorCode::new(Color::LightRed, Color::Blue));
+        writer.write_string("\x1b[0m");
+        assert_eq!(writer.color_code, ColorCode::new(Color::LightGray, Color::Black));
+    }
+
+    #[test_case]
+    fn escape_sequences_move_the_cursor() {
+        let mut writer = test_writer();
+        writer.write_string("\x1b[s\x1b[3;5Hab\x1b[2D\x1b[K");
+        assert_eq!((writer.row_position, writer.column_position), (2, 4));
+        assert_eq!(writer.display.read(2, 4).ascii_character, b' ');
+        assert_eq!(writer.display.read(2, 5).ascii_character, b' ');
+        writer.write_string("\x1b[u");
+        assert_eq!(writer.row_position, writer.height() - 1);
+    }
+}
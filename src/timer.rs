@@ -0,0 +1,35 @@
+use core::sync::atomic::{AtomicU64, Ordering};
+
+use x86_64::instructions::port::Port;
+
+use crate::status;
+
+pub const TICKS_PER_SECOND: u64 = 100;
+
+// Programmable interval timer, channel 0 is connected to IRQ0
+const PIT_FREQUENCY: u64 = 1_193_182;
+const PIT_CHANNEL_0_PORT: u16 = 0x40;
+const PIT_COMMAND_PORT: u16 = 0x43;
+// Channel 0, low then high byte of the divisor, mode 3 (square wave)
+const PIT_COMMAND: u8 = 0x36;
+
+// Timer interrupts since boot
+static TICKS: AtomicU64 = AtomicU64::new(0);
+
+// Let the PIT interrupt TICKS_PER_SECOND times a second instead of 18.2
+pub fn init() {
+    let divisor = (PIT_FREQUENCY / TICKS_PER_SECOND) as u16;
+    let mut command_port: Port<u8> = Port::new(PIT_COMMAND_PORT);
+    let mut channel_0_port: Port<u8> = Port::new(PIT_CHANNEL_0_PORT);
+    unsafe {
+        command_port.write(PIT_COMMAND);
+        channel_0_port.write((divisor & 0xFF) as u8);
+        channel_0_port.write((divisor >> 8) as u8);
+    }
+}
+
+// Called from the timer interrupt
+pub fn tick() {
+    let ticks = TICKS.fetch_add(1, Ordering::Relaxed) + 1;
+    status::tick(ticks);
+}
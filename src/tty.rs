@@ -0,0 +1,367 @@
+// Cooked mode line editing for Fgets: typed keys are echoed and can be edited
+// before the line is handed to the program. Ctrl+C and Ctrl+Z become signals
+// for the running program instead of input.
+
+use alloc::vec::Vec;
+use core::sync::atomic::{AtomicBool, Ordering};
+use pc_keyboard::{DecodedKey, KeyCode};
+use x86_64::instructions::interrupts;
+
+use crate::print;
+use crate::status;
+use crate::terminal::WRITER;
+
+// How many submitted lines are remembered for Up/Down
+const HISTORY_SIZE: usize = 32;
+
+// What Ctrl+letter decodes to
+pub const CTRL_C: char = '\u{3}';
+pub const CTRL_D: char = '\u{4}';
+pub const CTRL_Z: char = '\u{1a}';
+
+// Only while a program runs, the menu gets Ctrl+C and Ctrl+Z as keys
+static SIGNALS_ENABLED: AtomicBool = AtomicBool::new(false);
+static INTERRUPT_PENDING: AtomicBool = AtomicBool::new(false);
+static SUSPEND_PENDING: AtomicBool = AtomicBool::new(false);
+
+pub fn enable_signals(enabled: bool) {
+    INTERRUPT_PENDING.store(false, Ordering::Relaxed);
+    SUSPEND_PENDING.store(false, Ordering::Relaxed);
+    SIGNALS_ENABLED.store(enabled, Ordering::Relaxed);
+}
+
+// Called from the interrupt handlers for every key, returns whether the key
+// raised a signal and shouldn't reach the program
+pub fn handle_signal_key(key: DecodedKey) -> bool {
+    if !SIGNALS_ENABLED.load(Ordering::Relaxed) {
+        return false;
+    }
+    match key {
+        DecodedKey::Unicode(CTRL_C) => INTERRUPT_PENDING.store(true, Ordering::Relaxed),
+        // Ctrl+Z toggles, so a second one resumes
+        DecodedKey::Unicode(CTRL_Z) => {
+            SUSPEND_PENDING.fetch_xor(true, Ordering::Relaxed);
+        }
+        _ => return false,
+    }
+    true
+}
+
+pub fn interrupt_pending() -> bool {
+    INTERRUPT_PENDING.load(Ordering::Relaxed)
+}
+
+// Returns whether Ctrl+C was pressed since the last call
+pub fn take_interrupt() -> bool {
+    INTERRUPT_PENDING.swap(false, Ordering::Relaxed)
+}
+
+// Sleep while the program is suspended, until Ctrl+Z is pressed again or
+// Ctrl+C stops it. Typed keys are kept for the program.
+pub fn suspend_if_requested() {
+    if !SUSPEND_PENDING.load(Ordering::Relaxed) {
+        return;
+    }
+    status::set_suspended(true);
+    while SUSPEND_PENDING.load(Ordering::Relaxed) && !interrupt_pending() {
+        x86_64::instructions::interrupts::enable_and_hlt();
+    }
+    SUSPEND_PENDING.store(false, Ordering::Relaxed);
+    status::set_suspended(false);
+}
+
+// How a program reads stdin, set with the Ioctl syscall
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum InputMode {
+    // Fgets edits a line with echo, Getc returns typed characters
+    Cooked = 0,
+    // Fgets returns the keys as they are typed, without echo
+    Cbreak,
+    // Getc and Fgets return keyboard scancodes, including releases
+    Raw,
+}
+
+impl From<u16> for InputMode {
+    fn from(value: u16) -> InputMode {
+        match value {
+            0 => InputMode::Cooked,
+            1 => InputMode::Cbreak,
+            2 => InputMode::Raw,
+            _ => panic!("Invalid input mode {}", value)
+        }
+    }
+}
+
+// Why a line was handed to the program
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum LineEnd {
+    Enter,
+    // The line is as long as the program asked for
+    Full,
+    // F1 enters a NUL, which ends the input like it always did
+    Nul,
+    // Ctrl+D, the line is handed over without a newline
+    Eof,
+    // Ctrl+C, the line is thrown away
+    Interrupted,
+}
+
+#[derive(Debug)]
+pub struct LineEditor {
+    line: Vec<char>,
+    cursor: usize,             // Index in line
+    start: usize,              // Screen cell of the first character, row * width + column
+    width: usize,              // Of the screen, a line wraps to the next row
+    max_len: usize,
+    history: Vec<Vec<char>>,   // Oldest first
+    history_index: usize,      // history.len() while editing a new line
+    new_line: Vec<char>,       // The new line while browsing the history
+}
+
+impl LineEditor {
+    pub fn new() -> LineEditor {
+        LineEditor {
+            line: Vec::new(),
+            cursor: 0,
+            start: 0,
+            width: 1,
+            max_len: 0,
+            history: Vec::new(),
+            history_index: 0,
+            new_line: Vec::new(),
+        }
+    }
+
+    // Start editing an empty line of at most max_len characters
+    pub fn start(&mut self, max_len: usize) {
+        self.line.clear();
+        self.cursor = 0;
+        self.max_len = max_len;
+        self.history_index = self.history.len();
+        self.find_start(0);
+    }
+
+    pub fn line(&self) -> &[char] {
+        &self.line
+    }
+
+    // Handle a typed key, returns how the line ended once it is done
+    pub fn handle_key(&mut self, key: DecodedKey) -> Option<LineEnd> {
+        match key {
+            DecodedKey::Unicode('\n') => {
+                self.move_to(self.line.len());
+                print!("\n");
+                self.remember_line();
+                return Some(LineEnd::Enter);
+            }
+            DecodedKey::RawKey(KeyCode::F1) => return Some(LineEnd::Nul),
+            DecodedKey::Unicode(CTRL_D) => {
+                self.move_to(self.line.len());
+                self.remember_line();
+                return Some(LineEnd::Eof);
+            }
+            // Backspace and Delete
+            DecodedKey::Unicode('\u{8}') => {
+                if self.cursor > 0 {
+                    self.move_to(self.cursor - 1);
+                    self.delete_at_cursor();
+                }
+            }
+            DecodedKey::Unicode('\u{7f}') => self.delete_at_cursor(),
+            DecodedKey::RawKey(KeyCode::ArrowLeft) => {
+                if self.cursor > 0 {
+                    self.move_to(self.cursor - 1);
+                }
+            }
+            DecodedKey::RawKey(KeyCode::ArrowRight) => {
+                if self.cursor < self.line.len() {
+                    self.move_to(self.cursor + 1);
+                }
+            }
+            DecodedKey::RawKey(KeyCode::Home) => self.move_to(0),
+            DecodedKey::RawKey(KeyCode::End) => self.move_to(self.line.len()),
+            DecodedKey::RawKey(KeyCode::ArrowUp) => {
+                if self.history_index > 0 {
+                    if self.history_index == self.history.len() {
+                        self.new_line = self.line.clone();
+                    }
+                    self.history_index -= 1;
+                    let line = self.history[self.history_index].clone();
+                    self.replace_line(line);
+                }
+            }
+            DecodedKey::RawKey(KeyCode::ArrowDown) => {
+                if self.history_index < self.history.len() {
+                    self.history_index += 1;
+                    let line = if self.history_index == self.history.len() {
+                        self.new_line.clone()
+                    } else {
+                        self.history[self.history_index].clone()
+                    };
+                    self.replace_line(line);
+                }
+            }
+            DecodedKey::Unicode(c) if !c.is_control() => {
+                if self.line.len() < self.max_len {
+                    self.line.insert(self.cursor, c);
+                    self.cursor += 1;
+                    print!("{}", c);
+                    self.redraw_from(self.cursor, 0);
+                }
+                if self.line.len() == self.max_len {
+                    self.move_to(self.line.len());
+                    print!("\n");
+                    self.remember_line();
+                    return Some(LineEnd::Full);
+                }
+            }
+            _ => (),
+        }
+        None
+    }
+
+    // Move the cursor on the screen and in the line. Moving right prints the
+    // characters in between, so the screen wraps and scrolls like it did when
+    // they were typed.
+    fn move_to(&mut self, index: usize) {
+        if index < self.cursor {
+            self.place_cursor(index);
+        } else if index > self.cursor {
+            for &c in &self.line[self.cursor..index] {
+                print!("{}", c);
+            }
+            self.find_start(index);
+        }
+        self.cursor = index;
+    }
+
+    // The screen cursor is after the first index characters of the line, work
+    // out where the line starts. It moves up when the screen scrolls.
+    fn find_start(&mut self, index: usize) {
+        let (row, column, width) = interrupts::without_interrupts(|| {
+            let writer = WRITER.lock();
+            let (row, column) = writer.cursor_position();
+            (row, column, writer.width())
+        });
+        self.start = (row * width + column).saturating_sub(index);
+        self.width = width;
+    }
+
+    // Put the screen cursor on the character at index, which is on the screen.
+    // ESC [ C and ESC [ D stop at the edges, so move by rows and set the column.
+    fn place_cursor(&self, index: usize) {
+        let (row, _) = interrupts::without_interrupts(|| WRITER.lock().cursor_position());
+        let target = self.start + index;
+        let target_row = target / self.width;
+        if target_row < row {
+            print!("\x1b[{}A", row - target_row);
+        } else if target_row > row {
+            print!("\x1b[{}B", target_row - row);
+        }
+        print!("\x1b[{}G", target % self.width + 1);
+    }
+
+    fn delete_at_cursor(&mut self) {
+        if self.cursor < self.line.len() {
+            self.line.remove(self.cursor);
+            self.redraw_from(self.cursor, 1);
+        }
+    }
+
+    // Print the line from index on, and blanks over the erased characters
+    // behind it, then put the screen cursor back on the cursor
+    fn redraw_from(&mut self, index: usize, erased: usize) {
+        for &c in &self.line[index..] {
+            print!("{}", c);
+        }
+        for _ in 0..erased {
+            print!(" ");
+        }
+        let end = self.line.len() + erased;
+        self.find_start(end);
+        if self.cursor != end {
+            self.place_cursor(self.cursor);
+        }
+    }
+
+    fn replace_line(&mut self, mut line: Vec<char>) {
+        line.truncate(self.max_len);
+        let erased = self.line.len().saturating_sub(line.len());
+        self.move_to(0);
+        self.line = line;
+        self.cursor = self.line.len();
+        self.redraw_from(0, erased);
+    }
+
+    fn remember_line(&mut self) {
+        if self.line.is_empty() || self.history.last() == Some(&self.line) {
+            return;
+        }
+        if self.history.len() == HISTORY_SIZE {
+            self.history.remove(0);
+        }
+        self.history.push(self.line.clone());
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn type_keys(editor: &mut LineEditor, keys: &[DecodedKey]) -> Option<LineEnd> {
+        let mut end = None;
+        for &key in keys {
+            end = editor.handle_key(key);
+        }
+        end
+    }
+
+    #[test_case]
+    fn typed_keys_can_be_edited() {
+        let mut editor = LineEditor::new();
+        editor.start(10);
+        let end = type_keys(&mut editor, &[
+            DecodedKey::Unicode('a'),
+            DecodedKey::Unicode('x'),
+            DecodedKey::Unicode('b'),
+            DecodedKey::RawKey(KeyCode::ArrowLeft),
+            DecodedKey::Unicode('\u{8}'),
+            DecodedKey::Unicode('c'),
+            DecodedKey::Unicode('\n'),
+        ]);
+        assert_eq!(end, Some(LineEnd::Enter));
+        assert_eq!(editor.line(), &['a', 'c', 'b']);
+    }
+
+    #[test_case]
+    fn full_line_is_submitted() {
+        let mut editor = LineEditor::new();
+        editor.start(2);
+        assert_eq!(editor.handle_key(DecodedKey::Unicode('a')), None);
+        assert_eq!(editor.handle_key(DecodedKey::Unicode('b')), Some(LineEnd::Full));
+        editor.start(2);
+        type_keys(&mut editor, &[DecodedKey::RawKey(KeyCode::ArrowUp)]);
+        assert_eq!(editor.line(), &['a', 'b']);
+    }
+
+    #[test_case]
+    fn ctrl_d_ends_the_line() {
+        let mut editor = LineEditor::new();
+        editor.start(10);
+        let end = type_keys(&mut editor, &[DecodedKey::Unicode('a'), DecodedKey::Unicode(CTRL_D)]);
+        assert_eq!(end, Some(LineEnd::Eof));
+        assert_eq!(editor.line(), &['a']);
+    }
+
+    #[test_case]
+    fn history_is_recalled() {
+        let mut editor = LineEditor::new();
+        editor.start(10);
+        type_keys(&mut editor, &[DecodedKey::Unicode('a'), DecodedKey::Unicode('\n')]);
+        editor.start(10);
+        type_keys(&mut editor, &[DecodedKey::Unicode('b'), DecodedKey::RawKey(KeyCode::ArrowUp)]);
+        assert_eq!(editor.line(), &['a']);
+        type_keys(&mut editor, &[DecodedKey::RawKey(KeyCode::ArrowDown)]);
+        assert_eq!(editor.line(), &['b']);
+    }
+}
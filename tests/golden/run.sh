@@ -0,0 +1,62 @@
+#!/bin/sh
+# Boot every program that has a golden file in QEMU and compare its serial
+# output with the golden file.
+#
+# For a golden file tests/golden/<name>.expected the program is
+# tests/golden/modules/<name>.out if it exists, modules/<name>.out otherwise.
+# tests/golden/<name>.in is typed on the serial port as stdin.
+#
+# Usage: tests/golden/run.sh <kernel.bin>
+
+KERNEL=$1
+GOLDEN_DIR=`dirname $0`
+MK_GRUB_CFG=src/arch/x86_64/mkgrubcfg.sh
+BUILD_DIR=build/golden
+TIMEOUT=60
+# (0x10 << 1) | 1, see src/qemu.rs
+SUCCESS=33
+
+failed=0
+for expected in $GOLDEN_DIR/*.expected; do
+	name=`basename $expected .expected`
+
+	module=$GOLDEN_DIR/modules/$name.out
+	if [ ! -f "$module" ]; then
+		module=modules/$name.out
+	fi
+	input=$GOLDEN_DIR/$name.in
+	if [ ! -f "$input" ]; then
+		input=/dev/null
+	fi
+
+	# An ISO with only this program on it
+	work=$BUILD_DIR/$name
+	rm -rf $work
+	mkdir -p $work/modules $work/isofiles/boot/grub
+	cp $module $work/modules/$name.out
+	if [ -f "`dirname $module`/$name.cmdline" ]; then
+		cp "`dirname $module`/$name.cmdline" $work/modules
+	fi
+	cp $work/modules/$name.out $work/isofiles/boot
+	cp $KERNEL $work/isofiles/boot/kernel.bin
+	$MK_GRUB_CFG $work/modules > $work/isofiles/boot/grub/grub.cfg
+	grub-mkrescue -o $work/$name.iso $work/isofiles 2> /dev/null
+
+	timeout $TIMEOUT qemu-system-x86_64 -cdrom $work/$name.iso \
+		-serial stdio -display none -monitor none \
+		-device isa-debug-exit,iobase=0xf4,iosize=0x04 \
+		< $input > $work/output
+	status=$?
+
+	if [ $status -ne $SUCCESS ]; then
+		echo "FAIL $name (exit status $status)"
+		failed=1
+	elif ! diff -u $expected $work/output; then
+		echo "FAIL $name (output differs)"
+		failed=1
+	else
+		echo "ok   $name"
+	fi
+done
+
+exit $failed
@@ -0,0 +1,27 @@
+#!/bin/sh
+# Cargo runner for the kernel unit tests: boots the test binary in QEMU and
+# turns the isa-debug-exit status into a normal exit status.
+#
+# Usage: tests/run-kernel-tests.sh <test kernel>
+
+TEST_KERNEL=$1
+MK_GRUB_CFG=src/arch/x86_64/mkgrubcfg.sh
+BUILD_DIR=build/kernel-tests
+TIMEOUT=60
+# (0x10 << 1) | 1, see src/qemu.rs
+SUCCESS=33
+
+rm -rf $BUILD_DIR
+mkdir -p $BUILD_DIR/modules $BUILD_DIR/isofiles/boot/grub
+cp $TEST_KERNEL $BUILD_DIR/isofiles/boot/kernel.bin
+$MK_GRUB_CFG $BUILD_DIR/modules > $BUILD_DIR/isofiles/boot/grub/grub.cfg
+grub-mkrescue -o $BUILD_DIR/tests.iso $BUILD_DIR/isofiles 2> /dev/null
+
+timeout $TIMEOUT qemu-system-x86_64 -cdrom $BUILD_DIR/tests.iso \
+	-serial stdio -display none -monitor none \
+	-device isa-debug-exit,iobase=0xf4,iosize=0x04
+status=$?
+
+if [ $status -ne $SUCCESS ]; then
+	exit 1
+fi